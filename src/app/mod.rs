@@ -0,0 +1,57 @@
+pub trait Args: clap::Parser {
+    fn common(&self) -> &crate::cli::Args;
+}
+
+#[derive(Debug, Clone, clap::Parser)]
+pub struct Cli {
+    #[command(flatten)]
+    pub common: crate::cli::Args,
+}
+
+impl Args for Cli {
+    fn common(&self) -> &crate::cli::Args {
+        &self.common
+    }
+}
+
+pub fn run<A, F, Fut, E>(main: F) -> std::process::ExitCode
+where
+    A: Args,
+    F: FnOnce(A) -> Fut,
+    Fut: std::future::Future<Output = Result<(), E>>,
+    E: Into<crate::Error>,
+{
+    let args = A::parse();
+
+    if let Err(err) = args.common().log.setup() {
+        let err = crate::Error::from(err);
+        eprintln!("{err}");
+        return err.exit_code();
+    }
+
+    crate::panic::Hook::new().install();
+
+    let runtime = crate::rt::Runtime::new(
+        #[cfg(feature = "rt-threads")]
+        args.common().rt.threads(),
+    )
+    .build();
+    let runtime = match runtime {
+        Ok(runtime) => runtime,
+        Err(err) => return exit(&err.into()),
+    };
+
+    match runtime.block_on(main(args)) {
+        Ok(()) => {
+            tracing::info!("Application exited");
+            std::process::ExitCode::SUCCESS
+        }
+        Err(err) => exit(&err.into()),
+    }
+}
+
+fn exit(err: &crate::Error) -> std::process::ExitCode {
+    let kind = err.kind();
+    tracing::error!(error = %err, %kind, code = kind.code(), "Application failed");
+    err.exit_code()
+}
@@ -0,0 +1,87 @@
+#[derive(Debug, Clone, clap::Args)]
+pub struct Args {
+    #[cfg(feature = "log")]
+    #[command(flatten)]
+    pub log: Log,
+    #[cfg(feature = "rt-threads")]
+    #[command(flatten)]
+    pub rt: Rt,
+    #[cfg(any(feature = "server-h1", feature = "server-h2"))]
+    #[command(flatten)]
+    pub server: Server,
+}
+
+impl Args {
+    #[cfg(all(feature = "rt", any(feature = "server-h1", feature = "server-h2")))]
+    pub fn start(
+        &self,
+        router: impl Into<crate::server::Router>,
+    ) -> Result<(), crate::server::Error> {
+        crate::server::start_on(
+            router,
+            self.server.binds(),
+            crate::server::Config::default(),
+            #[cfg(feature = "rt-threads")]
+            self.rt.threads(),
+        )
+    }
+}
+
+#[cfg(feature = "log")]
+#[derive(Debug, Clone, clap::Args)]
+pub struct Log {
+    #[arg(long = "log-level", value_name = "LEVEL", help_heading = "Logging")]
+    pub level: Option<tracing::Level>,
+    #[arg(
+        long = "log-format",
+        value_name = "FORMAT",
+        value_enum,
+        help_heading = "Logging"
+    )]
+    pub format: Option<crate::log::Format>,
+}
+
+#[cfg(feature = "log")]
+impl Log {
+    pub fn setup(&self) -> Result<(), crate::log::Error> {
+        crate::log::setup_format(
+            crate::log::Stdout,
+            self.format.unwrap_or_default(),
+            self.level.unwrap_or(tracing::Level::INFO),
+        )
+    }
+}
+
+#[cfg(feature = "rt-threads")]
+#[derive(Debug, Clone, clap::Args)]
+pub struct Rt {
+    #[arg(long, value_parser = crate::rt::threads::parse, help_heading = "Runtime")]
+    pub threads: Option<crate::rt::Threads>,
+}
+
+#[cfg(feature = "rt-threads")]
+impl Rt {
+    #[must_use]
+    pub fn threads(&self) -> crate::rt::Threads {
+        self.threads.unwrap_or(crate::rt::Threads::Auto)
+    }
+}
+
+#[cfg(any(feature = "server-h1", feature = "server-h2"))]
+#[derive(Debug, Clone, clap::Args)]
+pub struct Server {
+    #[arg(long, value_name = "ADDR", help_heading = "Server")]
+    pub bind: Vec<std::net::SocketAddr>,
+}
+
+#[cfg(any(feature = "server-h1", feature = "server-h2"))]
+impl Server {
+    #[must_use]
+    pub fn binds(&self) -> Vec<std::net::SocketAddr> {
+        if self.bind.is_empty() {
+            vec![crate::server::BIND]
+        } else {
+            self.bind.clone()
+        }
+    }
+}
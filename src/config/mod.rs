@@ -0,0 +1,692 @@
+#[cfg(feature = "config-watch")]
+mod watch;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Could not read config file {}: {}", .0.display(), .1)]
+    Read(std::path::PathBuf, #[source] std::io::Error),
+    #[error("Unsupported config file format: {}", .0.display())]
+    Format(std::path::PathBuf),
+    #[error("Could not parse config file {}: {}", .0.display(), .1)]
+    Parse(std::path::PathBuf, String),
+    #[error("Invalid value for `{key}` from {origin}: {message}")]
+    Invalid {
+        key: String,
+        origin: String,
+        message: String,
+    },
+    #[error("Invalid config: {0}")]
+    Validation(String),
+    #[cfg(feature = "config-watch")]
+    #[error("Could not watch config: {0}")]
+    Watch(#[source] std::io::Error),
+}
+
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(default, bound(deserialize = "T: Default"))]
+pub struct Config<T = ()> {
+    #[cfg(feature = "log")]
+    pub log: Log,
+    #[cfg(feature = "rt-threads")]
+    pub rt: Rt,
+    #[cfg(any(feature = "server-h1", feature = "server-h2"))]
+    pub server: Server,
+    #[serde(skip)]
+    pub app: T,
+}
+
+#[cfg(feature = "cli")]
+impl<T> Config<T> {
+    #[cfg_attr(
+        not(any(
+            feature = "log",
+            feature = "rt-threads",
+            feature = "server-h1",
+            feature = "server-h2"
+        )),
+        allow(unused_variables, unused_mut)
+    )]
+    #[must_use]
+    pub fn merge(mut self, args: &crate::cli::Args) -> Self {
+        #[cfg(feature = "log")]
+        {
+            if let Some(level) = args.log.level {
+                self.log.level = level;
+            }
+            if let Some(format) = args.log.format {
+                self.log.format = format;
+            }
+        }
+
+        #[cfg(feature = "rt-threads")]
+        if let Some(threads) = args.rt.threads {
+            self.rt.threads = threads;
+        }
+
+        #[cfg(any(feature = "server-h1", feature = "server-h2"))]
+        if !args.server.bind.is_empty() {
+            self.server.bind.clone_from(&args.server.bind);
+        }
+
+        self
+    }
+}
+
+#[cfg(feature = "log")]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Log {
+    #[serde(deserialize_with = "level")]
+    pub level: tracing::Level,
+    pub format: crate::log::Format,
+}
+
+#[cfg(feature = "log")]
+impl Log {
+    pub fn setup(&self) -> Result<(), crate::log::Error> {
+        crate::log::setup_format(crate::log::Stdout, self.format, self.level)
+    }
+}
+
+#[cfg(feature = "log")]
+impl Default for Log {
+    fn default() -> Self {
+        Self {
+            level: tracing::Level::INFO,
+            format: crate::log::Format::default(),
+        }
+    }
+}
+
+#[cfg(feature = "rt-threads")]
+#[derive(Debug, Clone, Eq, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Rt {
+    pub threads: crate::rt::Threads,
+    pub thread_name: Option<String>,
+    pub stack_size: Option<usize>,
+    pub max_blocking_threads: Option<usize>,
+    #[serde(deserialize_with = "duration")]
+    pub blocking_keep_alive: Option<std::time::Duration>,
+    pub event_interval: Option<u32>,
+    #[cfg(feature = "rt-affinity")]
+    pub pin: bool,
+}
+
+#[cfg(feature = "rt-threads")]
+impl Rt {
+    #[must_use]
+    pub fn runtime(&self) -> crate::rt::Runtime {
+        let mut runtime = crate::rt::Runtime::new(self.threads);
+        if let Some(name) = &self.thread_name {
+            runtime = runtime.name(name.clone());
+        }
+        if let Some(stack_size) = self.stack_size {
+            runtime = runtime.stack_size(stack_size);
+        }
+        if let Some(max_blocking_threads) = self.max_blocking_threads {
+            runtime = runtime.max_blocking_threads(max_blocking_threads);
+        }
+        if let Some(blocking_keep_alive) = self.blocking_keep_alive {
+            runtime = runtime.blocking_keep_alive(blocking_keep_alive);
+        }
+        if let Some(event_interval) = self.event_interval {
+            runtime = runtime.event_interval(event_interval);
+        }
+        #[cfg(feature = "rt-affinity")]
+        {
+            runtime = runtime.pin(self.pin);
+        }
+        runtime
+    }
+}
+
+#[cfg(feature = "rt-threads")]
+impl Default for Rt {
+    fn default() -> Self {
+        Self {
+            threads: crate::rt::Threads::Auto,
+            thread_name: None,
+            stack_size: None,
+            max_blocking_threads: None,
+            blocking_keep_alive: None,
+            event_interval: None,
+            #[cfg(feature = "rt-affinity")]
+            pin: false,
+        }
+    }
+}
+
+#[cfg(any(feature = "server-h1", feature = "server-h2"))]
+#[derive(Debug, Clone, Eq, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Server {
+    #[serde(deserialize_with = "binds")]
+    pub bind: Vec<std::net::SocketAddr>,
+    #[serde(deserialize_with = "duration")]
+    pub shutdown_timeout: Option<std::time::Duration>,
+    pub max_connections: Option<usize>,
+    #[serde(deserialize_with = "duration")]
+    pub idle_timeout: Option<std::time::Duration>,
+}
+
+#[cfg(any(feature = "server-h1", feature = "server-h2"))]
+impl Server {
+    #[must_use]
+    pub fn config(&self) -> crate::server::Config {
+        crate::server::Config {
+            shutdown_timeout: self.shutdown_timeout,
+            connections: crate::server::Connections {
+                max: self.max_connections,
+                idle_timeout: self.idle_timeout,
+                ..crate::server::Connections::default()
+            },
+            ..crate::server::Config::default()
+        }
+    }
+}
+
+#[cfg(any(feature = "server-h1", feature = "server-h2"))]
+impl Default for Server {
+    fn default() -> Self {
+        Self {
+            bind: vec![crate::server::BIND],
+            shutdown_timeout: None,
+            max_connections: None,
+            idle_timeout: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Loader {
+    file: Option<std::path::PathBuf>,
+    prefix: String,
+    #[cfg(feature = "config-watch")]
+    interval: std::time::Duration,
+}
+
+impl Loader {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            file: None,
+            prefix: String::from("APP"),
+            #[cfg(feature = "config-watch")]
+            interval: std::time::Duration::from_secs(2),
+        }
+    }
+
+    #[must_use]
+    pub fn file(mut self, file: impl Into<std::path::PathBuf>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    #[must_use]
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    #[cfg(feature = "config-watch")]
+    #[must_use]
+    pub fn interval(mut self, interval: std::time::Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn load<T: serde::de::DeserializeOwned + Default>(&self) -> Result<Config<T>, Error> {
+        self.load_from(std::env::vars())
+    }
+
+    fn load_from<T: serde::de::DeserializeOwned + Default>(
+        &self,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Config<T>, Error> {
+        let mut table = match &self.file {
+            Some(file) => read(file)?,
+            None => toml::Table::new(),
+        };
+        let files = table
+            .keys()
+            .cloned()
+            .collect::<std::collections::HashSet<_>>();
+
+        let mut origins = std::collections::HashMap::new();
+        let mut raw = std::collections::HashMap::new();
+        let prefix = format!("{}_", self.prefix);
+        for (name, value) in vars {
+            let Some(key) = name.strip_prefix(&prefix) else {
+                continue;
+            };
+
+            let path = key.split("__").map(str::to_lowercase).collect::<Vec<_>>();
+            let parsed = parse(&value);
+            if !parsed.is_str() {
+                raw.insert(path.join("."), (path.clone(), value));
+            }
+            override_key(&mut table, &path, parsed);
+            origins.insert(path.join("."), name);
+        }
+
+        let mut config: Config<T> = self.deserialize(&mut table, &mut raw, &origins)?;
+
+        let mut app = table;
+        for section in ["log", "rt", "server"] {
+            app.remove(section);
+        }
+
+        if wants_table::<T>() {
+            if !app.is_empty() {
+                config.app = self.deserialize(&mut app, &mut raw, &origins)?;
+            }
+        } else if let Some(key) = app.keys().find(|key| files.contains(*key))
+            && let Some(origin) = self.origin()
+        {
+            return Err(Error::Invalid {
+                key: key.clone(),
+                origin,
+                message: String::from("unknown key"),
+            });
+        }
+
+        Ok(config)
+    }
+
+    fn deserialize<D: serde::de::DeserializeOwned>(
+        &self,
+        table: &mut toml::Table,
+        raw: &mut std::collections::HashMap<String, (Vec<String>, String)>,
+        origins: &std::collections::HashMap<String, String>,
+    ) -> Result<D, Error> {
+        loop {
+            let err = match serde_path_to_error::deserialize(toml::Value::Table(table.clone())) {
+                Ok(value) => return Ok(value),
+                Err(err) => err,
+            };
+
+            let key = err.path().to_string();
+            let Some((path, value)) = raw
+                .keys()
+                .find(|path| within(&key, path))
+                .cloned()
+                .and_then(|path| raw.remove(&path))
+            else {
+                return Err(self.invalid(err, origins));
+            };
+            override_key(table, &path, toml::Value::String(value));
+        }
+    }
+
+    fn invalid(
+        &self,
+        err: serde_path_to_error::Error<toml::de::Error>,
+        origins: &std::collections::HashMap<String, String>,
+    ) -> Error {
+        let key = err.path().to_string();
+        let origin = origins
+            .iter()
+            .find(|(path, _)| within(&key, path))
+            .map(|(_, name)| format!("environment variable {name}"))
+            .or_else(|| self.origin())
+            .unwrap_or_else(|| String::from("defaults"));
+
+        Error::Invalid {
+            key,
+            origin,
+            message: err.into_inner().message().to_owned(),
+        }
+    }
+
+    fn origin(&self) -> Option<String> {
+        self.file
+            .as_ref()
+            .map(|file| format!("config file {}", file.display()))
+    }
+}
+
+impl Default for Loader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn read(file: &std::path::Path) -> Result<toml::Table, Error> {
+    let content =
+        std::fs::read_to_string(file).map_err(|err| Error::Read(file.to_path_buf(), err))?;
+    let parse = |err: &dyn std::fmt::Display| Error::Parse(file.to_path_buf(), err.to_string());
+
+    match file.extension().and_then(std::ffi::OsStr::to_str) {
+        Some("toml") => content.parse().map_err(|err| parse(&err)),
+        #[cfg(feature = "config-json")]
+        Some("json") => serde_json::from_str(&content).map_err(|err| parse(&err)),
+        #[cfg(feature = "config-yaml")]
+        Some("yaml" | "yml") => serde_yaml::from_str(&content).map_err(|err| parse(&err)),
+        _ => Err(Error::Format(file.to_path_buf())),
+    }
+}
+
+fn wants_table<T: serde::de::DeserializeOwned>() -> bool {
+    struct Probe;
+
+    impl<'de> serde::Deserializer<'de> for Probe {
+        type Error = serde::de::value::Error;
+
+        fn deserialize_any<V: serde::de::Visitor<'de>>(
+            self,
+            _visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            Err(serde::de::Error::custom("table"))
+        }
+
+        fn deserialize_unit<V: serde::de::Visitor<'de>>(
+            self,
+            visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            visitor.visit_unit()
+        }
+
+        fn deserialize_unit_struct<V: serde::de::Visitor<'de>>(
+            self,
+            _name: &'static str,
+            visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            visitor.visit_unit()
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option newtype_struct seq tuple tuple_struct map struct
+            enum identifier ignored_any
+        }
+    }
+
+    T::deserialize(Probe).is_err()
+}
+
+fn within(key: &str, path: &str) -> bool {
+    key.strip_prefix(path)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[']))
+}
+
+fn parse(value: &str) -> toml::Value {
+    format!("value = {value}")
+        .parse::<toml::Table>()
+        .ok()
+        .and_then(|mut parsed| parsed.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(value.to_owned()))
+}
+
+fn override_key(table: &mut toml::Table, path: &[String], value: toml::Value) {
+    let Some((last, sections)) = path.split_last() else {
+        return;
+    };
+
+    let mut table = table;
+    for section in sections {
+        let entry = table
+            .entry(section.clone())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        if !entry.is_table() {
+            *entry = toml::Value::Table(toml::Table::new());
+        }
+        let toml::Value::Table(inner) = entry else {
+            unreachable!();
+        };
+        table = inner;
+    }
+
+    table.insert(last.clone(), value);
+}
+
+#[cfg(feature = "log")]
+fn level<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<tracing::Level, D::Error> {
+    let level = <String as serde::Deserialize>::deserialize(deserializer)?;
+    level.parse().map_err(|_| {
+        serde::de::Error::invalid_value(
+            serde::de::Unexpected::Str(&level),
+            &"one of trace, debug, info, warn or error",
+        )
+    })
+}
+
+#[cfg(any(feature = "rt-threads", feature = "server-h1", feature = "server-h2"))]
+fn duration<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<std::time::Duration>, D::Error> {
+    let duration = <String as serde::Deserialize>::deserialize(deserializer)?;
+    humantime::parse_duration(&duration)
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+#[cfg(any(feature = "server-h1", feature = "server-h2"))]
+fn binds<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<std::net::SocketAddr>, D::Error> {
+    struct Binds;
+
+    impl<'de> serde::de::Visitor<'de> for Binds {
+        type Value = Vec<std::net::SocketAddr>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("an address or a non-empty list of addresses")
+        }
+
+        fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+            value
+                .parse()
+                .map(|addr| vec![addr])
+                .map_err(|_| E::invalid_value(serde::de::Unexpected::Str(value), &self))
+        }
+
+        fn visit_seq<A: serde::de::SeqAccess<'de>>(
+            self,
+            mut seq: A,
+        ) -> Result<Self::Value, A::Error> {
+            let mut addrs = Vec::new();
+            while let Some(addr) = seq.next_element()? {
+                addrs.push(addr);
+            }
+            if addrs.is_empty() {
+                return Err(serde::de::Error::invalid_length(0, &self));
+            }
+            Ok(addrs)
+        }
+    }
+
+    deserializer.deserialize_any(Binds)
+}
+
+#[cfg(test)]
+mod tests {
+    #[derive(Debug, Default, PartialEq, serde::Deserialize)]
+    #[serde(default)]
+    struct App {
+        name: String,
+        db: Db,
+    }
+
+    #[derive(Debug, Default, PartialEq, serde::Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    struct Db {
+        password: String,
+        port: u16,
+        tls: bool,
+    }
+
+    #[derive(Debug, Default, serde::Deserialize)]
+    struct Unit;
+
+    fn vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(name, value)| ((*name).to_owned(), (*value).to_owned()))
+            .collect()
+    }
+
+    fn file(name: &str, content: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("boile-rs-{}-{name}", std::process::id()));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn invalid(err: super::Error) -> (String, String) {
+        match err {
+            super::Error::Invalid { key, origin, .. } => (key, origin),
+            err => panic!("unexpected error: {err}"),
+        }
+    }
+
+    #[test]
+    fn env_paths() {
+        let config = super::Loader::new()
+            .load_from::<App>(vars(&[
+                ("APP_NAME", "api"),
+                ("APP_DB__PORT", "5432"),
+                ("APP_DB__TLS", "true"),
+                ("OTHER_NAME", "other"),
+            ]))
+            .unwrap();
+        assert_eq!(config.app.name, "api");
+        assert_eq!(config.app.db.port, 5432);
+        assert!(config.app.db.tls);
+    }
+
+    #[test]
+    fn env_prefix() {
+        let config = super::Loader::new()
+            .prefix("SVC")
+            .load_from::<App>(vars(&[("SVC_NAME", "svc"), ("APP_NAME", "app")]))
+            .unwrap();
+        assert_eq!(config.app.name, "svc");
+    }
+
+    #[test]
+    fn env_strings() {
+        let config = super::Loader::new()
+            .load_from::<App>(vars(&[("APP_NAME", "2024"), ("APP_DB__PASSWORD", "12345")]))
+            .unwrap();
+        assert_eq!(config.app.name, "2024");
+        assert_eq!(config.app.db.password, "12345");
+
+        let config = super::Loader::new()
+            .load_from::<App>(vars(&[("APP_DB__PASSWORD", "true")]))
+            .unwrap();
+        assert_eq!(config.app.db.password, "true");
+    }
+
+    #[cfg(feature = "rt-threads")]
+    #[test]
+    fn env_builtin() {
+        let config = super::Loader::new()
+            .load_from::<()>(vars(&[
+                ("APP_RT__THREAD_NAME", "2024"),
+                ("APP_RT__STACK_SIZE", "65536"),
+            ]))
+            .unwrap();
+        assert_eq!(config.rt.thread_name.as_deref(), Some("2024"));
+        assert_eq!(config.rt.stack_size, Some(65536));
+    }
+
+    #[test]
+    fn env_invalid() {
+        let err = super::Loader::new()
+            .load_from::<App>(vars(&[("APP_DB__PORT", "many")]))
+            .unwrap_err();
+        assert_eq!(
+            invalid(err),
+            (
+                String::from("db.port"),
+                String::from("environment variable APP_DB__PORT")
+            )
+        );
+
+        let err = super::Loader::new()
+            .load_from::<App>(vars(&[("APP_DB__USER", "admin")]))
+            .unwrap_err();
+        assert_eq!(
+            invalid(err),
+            (
+                String::from("db.user"),
+                String::from("environment variable APP_DB__USER")
+            )
+        );
+    }
+
+    #[test]
+    fn file_invalid() {
+        let path = file("invalid.toml", "[db]\nport = \"many\"\n");
+        let err = super::Loader::new()
+            .file(&path)
+            .load_from::<App>(vars(&[("APP_DB__PASSWORD", "secret")]))
+            .unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            invalid(err),
+            (
+                String::from("db.port"),
+                format!("config file {}", path.display())
+            )
+        );
+    }
+
+    #[test]
+    fn env_overrides_file() {
+        let path = file("override.toml", "name = \"file\"\n[db]\nport = 1\n");
+        let config = super::Loader::new()
+            .file(&path)
+            .load_from::<App>(vars(&[("APP_DB__PORT", "2")]))
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(config.app.name, "file");
+        assert_eq!(config.app.db.port, 2);
+    }
+
+    #[test]
+    fn unit_leftovers() {
+        super::Loader::new()
+            .load_from::<()>(vars(&[("APP_ENV", "production"), ("APP_DEBUG", "1")]))
+            .unwrap();
+        super::Loader::new()
+            .load_from::<Unit>(vars(&[("APP_ENV", "production")]))
+            .unwrap();
+    }
+
+    #[test]
+    fn unit_unknown_file_keys() {
+        let path = file("unknown.toml", "extra = 1\n");
+        let err = super::Loader::new()
+            .file(&path)
+            .load_from::<()>(vars(&[("APP_ENV", "production")]))
+            .unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            invalid(err),
+            (
+                String::from("extra"),
+                format!("config file {}", path.display())
+            )
+        );
+    }
+
+    #[test]
+    fn wants_table() {
+        assert!(!super::wants_table::<()>());
+        assert!(!super::wants_table::<Unit>());
+        assert!(super::wants_table::<App>());
+        assert!(super::wants_table::<Option<App>>());
+        assert!(super::wants_table::<
+            std::collections::HashMap<String, String>,
+        >());
+    }
+
+    #[test]
+    fn within() {
+        assert!(super::within("db.port", "db.port"));
+        assert!(super::within("db.port", "db"));
+        assert!(super::within("hosts[0]", "hosts"));
+        assert!(!super::within("db.port", "db.po"));
+        assert!(!super::within("database", "db"));
+    }
+}
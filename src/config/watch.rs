@@ -0,0 +1,81 @@
+impl super::Loader {
+    pub fn watch<T>(self) -> Result<tokio::sync::watch::Receiver<super::Config<T>>, super::Error>
+    where
+        T: serde::de::DeserializeOwned + Default + Send + Sync + 'static,
+    {
+        self.watch_with(|_| Ok::<(), std::convert::Infallible>(()))
+    }
+
+    pub fn watch_with<T, F, E>(
+        self,
+        validate: F,
+    ) -> Result<tokio::sync::watch::Receiver<super::Config<T>>, super::Error>
+    where
+        T: serde::de::DeserializeOwned + Default + Send + Sync + 'static,
+        F: Fn(&super::Config<T>) -> Result<(), E> + Send + 'static,
+        E: std::fmt::Display,
+    {
+        let load = move |loader: &Self| {
+            let config = loader.load::<T>()?;
+            validate(&config).map_err(|err| super::Error::Validation(err.to_string()))?;
+            Ok::<_, super::Error>(config)
+        };
+
+        let config = load(&self)?;
+        #[cfg(unix)]
+        let mut hangup = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())
+            .map_err(super::Error::Watch)?;
+
+        let (sender, receiver) = tokio::sync::watch::channel(config);
+        tokio::spawn(async move {
+            let mut last = self.file.as_deref().and_then(stamp);
+            let mut interval = tokio::time::interval(self.interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                #[cfg(unix)]
+                let hangup = hangup.recv();
+                #[cfg(not(unix))]
+                let hangup = std::future::pending::<Option<()>>();
+
+                #[cfg_attr(not(feature = "log"), allow(unused_variables))]
+                let reason = tokio::select! {
+                    () = sender.closed() => return,
+                    Some(()) = hangup => "SIGHUP",
+                    _ = interval.tick() => {
+                        let current = self.file.as_deref().and_then(stamp);
+                        if current == last {
+                            continue;
+                        }
+                        last = current;
+                        "file changed"
+                    }
+                };
+
+                #[cfg(feature = "log")]
+                tracing::info!(reason, "Reloading config");
+
+                match load(&self) {
+                    Ok(config) => {
+                        sender.send_replace(config);
+
+                        #[cfg(feature = "log")]
+                        tracing::info!("Config reloaded");
+                    }
+                    #[cfg_attr(not(feature = "log"), allow(unused_variables))]
+                    Err(err) => {
+                        #[cfg(feature = "log")]
+                        tracing::error!(error = %err, "Ignoring invalid config");
+                    }
+                }
+            }
+        });
+
+        Ok(receiver)
+    }
+}
+
+fn stamp(file: &std::path::Path) -> Option<(std::time::SystemTime, u64)> {
+    let metadata = std::fs::metadata(file).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
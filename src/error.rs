@@ -0,0 +1,154 @@
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[cfg(feature = "log")]
+    #[error(transparent)]
+    Log(#[from] crate::log::Error),
+    #[cfg(feature = "rt")]
+    #[error(transparent)]
+    Runtime(#[from] crate::rt::Error),
+    #[cfg(feature = "rt-threads")]
+    #[error(transparent)]
+    Threads(#[from] crate::rt::threads::Error),
+    #[cfg(feature = "rt-compute")]
+    #[error(transparent)]
+    Compute(#[from] crate::rt::compute::Error),
+    #[cfg(feature = "rt-shutdown")]
+    #[error(transparent)]
+    Shutdown(#[from] crate::rt::shutdown::Error),
+    #[cfg(all(feature = "rt-signal", unix))]
+    #[error(transparent)]
+    Signal(#[from] crate::rt::signal::Error),
+    #[cfg(all(feature = "systemd", unix))]
+    #[error(transparent)]
+    Systemd(#[from] crate::systemd::Error),
+    #[cfg(feature = "config")]
+    #[error(transparent)]
+    Config(#[from] crate::config::Error),
+    #[cfg(any(feature = "server-h1", feature = "server-h2"))]
+    #[error(transparent)]
+    Server(#[from] crate::server::Error),
+    #[error(transparent)]
+    Other(#[from] Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl Error {
+    pub fn other(err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Self::Other(err.into())
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> Kind {
+        match self {
+            #[cfg(feature = "log")]
+            Self::Log(_) => Kind::Internal,
+            #[cfg(feature = "rt")]
+            Self::Runtime(_) => Kind::Os,
+            #[cfg(feature = "rt-threads")]
+            Self::Threads(_) => Kind::Config,
+            #[cfg(feature = "rt-compute")]
+            Self::Compute(crate::rt::compute::Error::Spawn(_)) => Kind::Os,
+            #[cfg(feature = "rt-compute")]
+            Self::Compute(crate::rt::compute::Error::Panicked(_)) => Kind::Internal,
+            #[cfg(feature = "rt-compute")]
+            Self::Compute(_) => Kind::Unavailable,
+            #[cfg(feature = "rt-shutdown")]
+            Self::Shutdown(_) => Kind::Os,
+            #[cfg(all(feature = "rt-signal", unix))]
+            Self::Signal(_) => Kind::Os,
+            #[cfg(all(feature = "systemd", unix))]
+            Self::Systemd(_) => Kind::Os,
+            #[cfg(feature = "config")]
+            Self::Config(crate::config::Error::Read(_, err)) => io(err, Kind::Config),
+            #[cfg(feature = "config-watch")]
+            Self::Config(crate::config::Error::Watch(_)) => Kind::Os,
+            #[cfg(feature = "config")]
+            Self::Config(_) => Kind::Config,
+            #[cfg(any(feature = "server-h1", feature = "server-h2"))]
+            Self::Server(err) => server(err),
+            Self::Other(_) => Kind::Other,
+        }
+    }
+
+    #[must_use]
+    pub fn exit_code(&self) -> std::process::ExitCode {
+        self.kind().into()
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Kind {
+    Config,
+    AddrInUse,
+    PermissionDenied,
+    Unavailable,
+    Io,
+    Os,
+    Internal,
+    Other,
+}
+
+impl Kind {
+    #[must_use]
+    pub fn code(self) -> u8 {
+        match self {
+            Self::Config => 78,
+            Self::PermissionDenied => 77,
+            Self::Io => 74,
+            Self::Os => 71,
+            Self::Internal => 70,
+            Self::AddrInUse | Self::Unavailable => 69,
+            Self::Other => 1,
+        }
+    }
+}
+
+impl std::fmt::Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Config => f.write_str("config"),
+            Self::AddrInUse => f.write_str("address in use"),
+            Self::PermissionDenied => f.write_str("permission denied"),
+            Self::Unavailable => f.write_str("unavailable"),
+            Self::Io => f.write_str("io"),
+            Self::Os => f.write_str("os"),
+            Self::Internal => f.write_str("internal"),
+            Self::Other => f.write_str("other"),
+        }
+    }
+}
+
+impl From<Kind> for std::process::ExitCode {
+    fn from(value: Kind) -> Self {
+        Self::from(value.code())
+    }
+}
+
+#[cfg(any(feature = "config", feature = "server-h1", feature = "server-h2"))]
+fn io(err: &std::io::Error, fallback: Kind) -> Kind {
+    match err.kind() {
+        std::io::ErrorKind::AddrInUse => Kind::AddrInUse,
+        std::io::ErrorKind::PermissionDenied => Kind::PermissionDenied,
+        std::io::ErrorKind::AddrNotAvailable
+        | std::io::ErrorKind::ConnectionRefused
+        | std::io::ErrorKind::NotConnected => Kind::Unavailable,
+        _ => fallback,
+    }
+}
+
+#[cfg(any(feature = "server-h1", feature = "server-h2"))]
+fn server(err: &crate::server::Error) -> Kind {
+    match err {
+        #[cfg(feature = "rt-shutdown")]
+        crate::server::Error::Shutdown(_) => Kind::Os,
+        #[cfg(feature = "rt")]
+        crate::server::Error::Runtime(_) => Kind::Os,
+        crate::server::Error::Config(_) => Kind::Config,
+        crate::server::Error::TaskJoin(_) => Kind::Internal,
+        crate::server::Error::Server(_) => Kind::Io,
+        crate::server::Error::Io(err) => io(err, Kind::Io),
+        crate::server::Error::Servers(failures) => failures
+            .0
+            .first()
+            .map_or(Kind::Unavailable, |(_, err)| server(err)),
+    }
+}
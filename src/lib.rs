@@ -1,5 +1,23 @@
 #![allow(clippy::missing_errors_doc)]
 
+mod error;
+#[cfg(any(
+    feature = "rt-compute",
+    all(feature = "log", any(feature = "panic", feature = "rt-tasks"))
+))]
+mod unwind;
+
+pub use error::{Error, Kind};
+
+#[cfg(feature = "app")]
+pub mod app;
+
+#[cfg(feature = "cli")]
+pub mod cli;
+
+#[cfg(feature = "config")]
+pub mod config;
+
 #[cfg(feature = "log")]
 pub mod log;
 
@@ -9,5 +27,8 @@ pub mod rt;
 #[cfg(feature = "panic")]
 pub mod panic;
 
+#[cfg(all(feature = "systemd", unix))]
+pub mod systemd;
+
 #[cfg(any(feature = "server-h1", feature = "server-h2"))]
 pub mod server;
@@ -4,7 +4,7 @@ pub mod tracing;
 #[cfg(feature = "log-tower")]
 pub mod tower;
 
-pub use output::{Output, Stderr, Stdout};
+pub use output::{Output, Plain, Stderr, Stdout};
 
 #[derive(Debug, thiserror::Error)]
 #[error("Could not set tracing logger: {0}")]
@@ -19,3 +19,27 @@ pub fn setup<O: Output>(output: O, level: ::tracing::Level) -> Result<(), Error>
 
     ::tracing::subscriber::set_global_default(subscriber).map_err(Into::into)
 }
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Format {
+    #[default]
+    Pretty,
+    Plain,
+}
+
+pub fn setup_format<O: Output>(
+    output: O,
+    format: Format,
+    level: ::tracing::Level,
+) -> Result<(), Error> {
+    match format {
+        Format::Pretty => setup(output, level),
+        Format::Plain => setup(Plain(output), level),
+    }
+}
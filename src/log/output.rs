@@ -19,3 +19,48 @@ impl Output for Stderr {
         std::io::stderr().lock()
     }
 }
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Plain<O>(pub O);
+
+impl<O: Output> Output for Plain<O> {
+    fn lock(&self) -> impl std::io::Write {
+        Strip {
+            inner: self.0.lock(),
+            escape: false,
+        }
+    }
+}
+
+struct Strip<W> {
+    inner: W,
+    escape: bool,
+}
+
+impl<W: std::io::Write> std::io::Write for Strip<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut rest = buf;
+        while !rest.is_empty() {
+            if self.escape {
+                let Some(end) = rest.iter().position(u8::is_ascii_alphabetic) else {
+                    break;
+                };
+                self.escape = false;
+                rest = &rest[end + 1..];
+            } else {
+                let Some(start) = rest.iter().position(|byte| *byte == 0x1b) else {
+                    self.inner.write_all(rest)?;
+                    break;
+                };
+                self.inner.write_all(&rest[..start])?;
+                self.escape = true;
+                rest = &rest[start + 1..];
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,69 @@
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum Action {
+    #[default]
+    Continue,
+    Abort,
+    #[cfg(feature = "rt-shutdown")]
+    Shutdown,
+}
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct Hook {
+    backtrace: bool,
+    action: Action,
+}
+
+impl Hook {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn backtrace(mut self, backtrace: bool) -> Self {
+        self.backtrace = backtrace;
+        self
+    }
+
+    #[must_use]
+    pub fn action(mut self, action: Action) -> Self {
+        self.action = action;
+        self
+    }
+
+    pub fn install(self) {
+        std::panic::set_hook(Box::new(move |info| {
+            if super::caught(info) {
+                return;
+            }
+
+            self.report(info);
+
+            match self.action {
+                Action::Continue => {}
+                Action::Abort => std::process::abort(),
+                #[cfg(feature = "rt-shutdown")]
+                Action::Shutdown => crate::rt::shutdown::token().cancel(),
+            }
+        }));
+    }
+
+    fn report(self, info: &std::panic::PanicHookInfo<'_>) {
+        let thread = std::thread::current();
+        let backtrace = if self.backtrace {
+            std::backtrace::Backtrace::force_capture()
+        } else {
+            std::backtrace::Backtrace::capture()
+        };
+        let backtrace = (backtrace.status() == std::backtrace::BacktraceStatus::Captured)
+            .then(|| tracing::field::display(&backtrace));
+
+        tracing::error!(
+            panic = crate::unwind::message(info.payload()),
+            thread = thread.name().unwrap_or("<unnamed>"),
+            location = info.location().map(tracing::field::display),
+            backtrace,
+            "Thread panicked"
+        );
+    }
+}
@@ -0,0 +1,215 @@
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum Format {
+    #[default]
+    Plain,
+    Json,
+}
+
+#[derive(Debug, Clone)]
+pub struct Layer {
+    format: Format,
+    request_id: http::HeaderName,
+}
+
+impl Layer {
+    #[must_use]
+    pub fn new() -> Self {
+        #[cfg(feature = "log")]
+        super::capture();
+
+        Self {
+            format: Format::default(),
+            request_id: http::HeaderName::from_static("x-request-id"),
+        }
+    }
+
+    #[must_use]
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    #[must_use]
+    pub fn request_id(mut self, header: http::HeaderName) -> Self {
+        self.request_id = header;
+        self
+    }
+}
+
+impl Default for Layer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> tower::Layer<S> for Layer {
+    type Service = Catch<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Catch {
+            inner,
+            format: self.format,
+            request_id: self.request_id.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Catch<S> {
+    inner: S,
+    format: Format,
+    request_id: http::HeaderName,
+}
+
+impl<S, B, R> tower::Service<http::Request<B>> for Catch<S>
+where
+    S: tower::Service<http::Request<B>, Response = http::Response<R>>,
+    R: From<String>,
+{
+    type Response = http::Response<R>;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future>;
+
+    fn poll_ready(
+        &mut self,
+        ctx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(ctx)
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        let request_id = request
+            .headers()
+            .get(&self.request_id)
+            .and_then(|id| id.to_str().ok())
+            .map(ToOwned::to_owned);
+
+        let state = {
+            #[cfg(feature = "log")]
+            let _catching = super::Catching::enter();
+            match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                self.inner.call(request)
+            })) {
+                Ok(future) => State::Running(Box::pin(future)),
+                Err(payload) => State::Panicked(payload),
+            }
+        };
+
+        ResponseFuture {
+            state,
+            format: self.format,
+            request_id,
+        }
+    }
+}
+
+pub struct ResponseFuture<F> {
+    state: State<F>,
+    format: Format,
+    request_id: Option<String>,
+}
+
+enum State<F> {
+    Running(std::pin::Pin<Box<F>>),
+    Panicked(Box<dyn std::any::Any + Send>),
+    Done,
+}
+
+impl<F, R, E> std::future::Future for ResponseFuture<F>
+where
+    F: std::future::Future<Output = Result<http::Response<R>, E>>,
+    R: From<String>,
+{
+    type Output = Result<http::Response<R>, E>;
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        ctx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        let payload = match std::mem::replace(&mut self.state, State::Done) {
+            State::Running(mut future) => {
+                #[cfg(feature = "log")]
+                let catching = super::Catching::enter();
+                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    future.as_mut().poll(ctx)
+                }));
+                #[cfg(feature = "log")]
+                drop(catching);
+
+                match result {
+                    Ok(std::task::Poll::Pending) => {
+                        self.state = State::Running(future);
+                        return std::task::Poll::Pending;
+                    }
+                    Ok(std::task::Poll::Ready(response)) => {
+                        return std::task::Poll::Ready(response);
+                    }
+                    Err(payload) => payload,
+                }
+            }
+            State::Panicked(payload) => payload,
+            State::Done => panic!("ResponseFuture polled after completion"),
+        };
+
+        std::task::Poll::Ready(Ok(self.respond(&*payload)))
+    }
+}
+
+impl<F> ResponseFuture<F> {
+    #[cfg_attr(not(feature = "log"), allow(clippy::unused_self, unused_variables))]
+    fn respond<R: From<String>>(&self, payload: &(dyn std::any::Any + Send)) -> http::Response<R> {
+        #[cfg(feature = "log")]
+        {
+            let caught = super::CAUGHT.take();
+            let location = caught
+                .as_ref()
+                .and_then(|caught| caught.location.as_deref());
+            let backtrace = caught
+                .as_ref()
+                .map(|caught| &caught.backtrace)
+                .filter(|backtrace| backtrace.status() == std::backtrace::BacktraceStatus::Captured)
+                .map(tracing::field::display);
+
+            tracing::error!(
+                panic = crate::unwind::message(payload),
+                location,
+                request_id = self.request_id.as_deref(),
+                backtrace,
+                "Request handler panicked"
+            );
+        }
+
+        let status = http::StatusCode::INTERNAL_SERVER_ERROR;
+        let reason = status.canonical_reason().unwrap_or_default();
+        let (content, body) = match (self.format, &self.request_id) {
+            (Format::Plain, None) => ("text/plain; charset=utf-8", format!("{reason}\n")),
+            (Format::Plain, Some(id)) => (
+                "text/plain; charset=utf-8",
+                format!("{reason}\nRequest id: {id}\n"),
+            ),
+            (Format::Json, None) => (
+                "application/problem+json",
+                format!(
+                    r#"{{"type":"about:blank","title":"{reason}","status":{}}}"#,
+                    status.as_u16()
+                ),
+            ),
+            (Format::Json, Some(id)) => (
+                "application/problem+json",
+                format!(
+                    r#"{{"type":"about:blank","title":"{reason}","status":{},"request_id":"{}"}}"#,
+                    status.as_u16(),
+                    id.replace('\\', "\\\\").replace('"', "\\\""),
+                ),
+            ),
+        };
+
+        let mut response = http::Response::new(R::from(body));
+        *response.status_mut() = status;
+        response.headers_mut().insert(
+            http::header::CONTENT_TYPE,
+            http::HeaderValue::from_static(content),
+        );
+        response
+    }
+}
@@ -1,3 +1,69 @@
+#[cfg(feature = "log")]
+mod hook;
+mod layer;
+
 #[allow(clippy::module_name_repetitions)]
 pub use tower_http::catch_panic::CatchPanic;
 pub use tower_http::catch_panic::CatchPanicLayer;
+
+#[cfg(feature = "log")]
+pub use hook::{Action, Hook};
+pub use layer::{Catch, Format, Layer, ResponseFuture};
+
+#[cfg(feature = "log")]
+thread_local! {
+    static CATCHING: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    static CAUGHT: std::cell::RefCell<Option<Caught>> = const { std::cell::RefCell::new(None) };
+}
+
+#[cfg(feature = "log")]
+static HOOK: std::sync::Once = std::sync::Once::new();
+
+#[cfg(feature = "log")]
+struct Caught {
+    location: Option<String>,
+    backtrace: std::backtrace::Backtrace,
+}
+
+#[cfg(feature = "log")]
+fn capture() {
+    HOOK.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if !caught(info) {
+                previous(info);
+            }
+        }));
+    });
+}
+
+#[cfg(feature = "log")]
+fn caught(info: &std::panic::PanicHookInfo<'_>) -> bool {
+    if CATCHING.get() == 0 {
+        return false;
+    }
+
+    CAUGHT.set(Some(Caught {
+        location: info.location().map(ToString::to_string),
+        backtrace: std::backtrace::Backtrace::capture(),
+    }));
+    true
+}
+
+#[cfg(feature = "log")]
+struct Catching;
+
+#[cfg(feature = "log")]
+impl Catching {
+    fn enter() -> Self {
+        CATCHING.set(CATCHING.get() + 1);
+        Self
+    }
+}
+
+#[cfg(feature = "log")]
+impl Drop for Catching {
+    fn drop(&mut self) {
+        CATCHING.set(CATCHING.get() - 1);
+    }
+}
@@ -0,0 +1,268 @@
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Could not spawn compute thread: {0}")]
+    Spawn(#[source] std::io::Error),
+    #[error("Compute queue is full")]
+    Full,
+    #[error("Compute pool is closed")]
+    Closed,
+    #[error("Compute job panicked: {0}")]
+    Panicked(String),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Compute {
+    workers: usize,
+    queue: usize,
+    name: String,
+    stack_size: Option<usize>,
+}
+
+impl Compute {
+    #[must_use]
+    pub fn new(workers: usize) -> Self {
+        Self {
+            workers: workers.max(1),
+            queue: 1024,
+            name: String::from("compute"),
+            stack_size: None,
+        }
+    }
+
+    #[must_use]
+    pub fn queue(mut self, queue: usize) -> Self {
+        self.queue = queue.max(1);
+        self
+    }
+
+    #[must_use]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    #[must_use]
+    pub fn stack_size(mut self, stack_size: usize) -> Self {
+        self.stack_size = Some(stack_size);
+        self
+    }
+
+    pub fn build(&self) -> Result<Pool, Error> {
+        #[cfg(feature = "log")]
+        tracing::info!(
+            workers = self.workers,
+            queue = self.queue,
+            name = %self.name,
+            "Building compute pool"
+        );
+
+        let (sender, receiver) = std::sync::mpsc::channel::<Job>();
+        let receiver = std::sync::Arc::new(std::sync::Mutex::new(receiver));
+
+        for id in 0..self.workers {
+            let mut thread = std::thread::Builder::new().name(format!("{}-{id}", self.name));
+            if let Some(stack_size) = self.stack_size {
+                thread = thread.stack_size(stack_size);
+            }
+
+            let receiver = receiver.clone();
+            thread
+                .spawn(move || work(&receiver))
+                .map_err(Error::Spawn)?;
+        }
+
+        Ok(Pool(std::sync::Arc::new(Inner {
+            sender,
+            permits: std::sync::Arc::new(tokio::sync::Semaphore::new(self.queue)),
+            counters: std::sync::Arc::new(Counters::default()),
+            workers: self.workers,
+            capacity: self.queue,
+        })))
+    }
+}
+
+#[cfg(feature = "rt-threads")]
+impl From<super::Threads> for Compute {
+    fn from(value: super::Threads) -> Self {
+        Self::new(value.workers())
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+fn work(receiver: &std::sync::Mutex<std::sync::mpsc::Receiver<Job>>) {
+    loop {
+        let job = match receiver.lock() {
+            Ok(receiver) => receiver.recv(),
+            Err(_) => return,
+        };
+        match job {
+            Ok(job) => job(),
+            Err(_) => return,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    queued: std::sync::atomic::AtomicUsize,
+    running: std::sync::atomic::AtomicUsize,
+    completed: std::sync::atomic::AtomicU64,
+    panicked: std::sync::atomic::AtomicU64,
+    rejected: std::sync::atomic::AtomicU64,
+}
+
+#[derive(Debug)]
+struct Inner {
+    sender: std::sync::mpsc::Sender<Job>,
+    permits: std::sync::Arc<tokio::sync::Semaphore>,
+    counters: std::sync::Arc<Counters>,
+    workers: usize,
+    capacity: usize,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Metrics {
+    pub workers: usize,
+    pub capacity: usize,
+    pub queued: usize,
+    pub running: usize,
+    pub completed: u64,
+    pub panicked: u64,
+    pub rejected: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Pool(std::sync::Arc<Inner>);
+
+impl Pool {
+    pub async fn spawn<F, R>(&self, f: F) -> Result<R, Error>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let permit = match self.0.permits.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(tokio::sync::TryAcquireError::NoPermits) => {
+                #[cfg(feature = "log")]
+                tracing::debug!(queued = self.queued(), "Waiting for compute queue");
+
+                self.0
+                    .permits
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .map_err(|_| Error::Closed)?
+            }
+            Err(tokio::sync::TryAcquireError::Closed) => return Err(Error::Closed),
+        };
+
+        self.submit(permit, f).await
+    }
+
+    pub async fn try_spawn<F, R>(&self, f: F) -> Result<R, Error>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let permit = match self.0.permits.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(tokio::sync::TryAcquireError::NoPermits) => {
+                self.0
+                    .counters
+                    .rejected
+                    .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                return Err(Error::Full);
+            }
+            Err(tokio::sync::TryAcquireError::Closed) => return Err(Error::Closed),
+        };
+
+        self.submit(permit, f).await
+    }
+
+    #[must_use]
+    pub fn queued(&self) -> usize {
+        self.0
+            .counters
+            .queued
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    #[must_use]
+    pub fn metrics(&self) -> Metrics {
+        let counters = &self.0.counters;
+        Metrics {
+            workers: self.0.workers,
+            capacity: self.0.capacity,
+            queued: counters.queued.load(std::sync::atomic::Ordering::Relaxed),
+            running: counters.running.load(std::sync::atomic::Ordering::Relaxed),
+            completed: counters
+                .completed
+                .load(std::sync::atomic::Ordering::Relaxed),
+            panicked: counters.panicked.load(std::sync::atomic::Ordering::Relaxed),
+            rejected: counters.rejected.load(std::sync::atomic::Ordering::Relaxed),
+        }
+    }
+
+    async fn submit<F, R>(
+        &self,
+        permit: tokio::sync::OwnedSemaphorePermit,
+        f: F,
+    ) -> Result<R, Error>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (sender, receiver) = tokio::sync::oneshot::channel();
+        let counters = self.0.counters.clone();
+        #[cfg(feature = "log")]
+        let span = tracing::Span::current();
+
+        counters
+            .queued
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+
+        let job: Job = Box::new(move || {
+            counters
+                .queued
+                .fetch_sub(1, std::sync::atomic::Ordering::Relaxed);
+            drop(permit);
+
+            if sender.is_closed() {
+                return;
+            }
+
+            counters
+                .running
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            #[cfg(feature = "log")]
+            let result =
+                std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| span.in_scope(f)));
+            #[cfg(not(feature = "log"))]
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+            counters
+                .running
+                .fetch_sub(1, std::sync::atomic::Ordering::Relaxed);
+            if result.is_ok() {
+                &counters.completed
+            } else {
+                &counters.panicked
+            }
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+
+            let _ =
+                sender.send(result.map_err(|payload| {
+                    Error::Panicked(crate::unwind::message(&*payload).to_owned())
+                }));
+        });
+
+        if self.0.sender.send(job).is_err() {
+            self.0
+                .counters
+                .queued
+                .fetch_sub(1, std::sync::atomic::Ordering::Relaxed);
+            return Err(Error::Closed);
+        }
+        receiver.await.map_err(|_| Error::Closed)?
+    }
+}
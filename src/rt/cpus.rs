@@ -0,0 +1,355 @@
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Cpus {
+    pub(super) allowed: Option<Vec<usize>>,
+    pub(super) quota: Option<f64>,
+}
+
+impl Cpus {
+    pub(super) fn detect() -> Self {
+        #[cfg(target_os = "linux")]
+        return Self {
+            allowed: std::fs::read_to_string("/proc/self/status")
+                .ok()
+                .and_then(|status| affinity(&status)),
+            quota: std::fs::read_to_string("/proc/self/cgroup")
+                .ok()
+                .zip(std::fs::read_to_string("/proc/self/mountinfo").ok())
+                .and_then(|(cgroups, mounts)| {
+                    quota(&cgroups, &mounts, |path| std::fs::read_to_string(path).ok())
+                }),
+        };
+
+        #[cfg(not(target_os = "linux"))]
+        Self {
+            allowed: None,
+            quota: None,
+        }
+    }
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub(super) fn count(&self) -> usize {
+        let cpus = self.allowed.as_ref().map_or_else(
+            || std::thread::available_parallelism().map_or(1, std::num::NonZero::get),
+            Vec::len,
+        );
+
+        match self.quota {
+            Some(quota) => cpus.min(quota.ceil() as usize),
+            None => cpus,
+        }
+        .max(1)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn affinity(status: &str) -> Option<Vec<usize>> {
+    let list = status
+        .lines()
+        .find_map(|line| line.strip_prefix("Cpus_allowed_list:"))?;
+
+    let mut cpus = Vec::new();
+    for range in list.trim().split(',').filter(|range| !range.is_empty()) {
+        match range.split_once('-') {
+            Some((start, end)) => cpus.extend(start.parse::<usize>().ok()?..=end.parse().ok()?),
+            None => cpus.push(range.parse().ok()?),
+        }
+    }
+
+    (!cpus.is_empty()).then_some(cpus)
+}
+
+#[cfg(target_os = "linux")]
+fn quota(
+    cgroups: &str,
+    mounts: &str,
+    read: impl Fn(&std::path::Path) -> Option<String>,
+) -> Option<f64> {
+    let mut quota = None::<f64>;
+    for line in cgroups.lines() {
+        let mut fields = line.splitn(3, ':');
+        let (Some(id), Some(controllers), Some(path)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+
+        let v2 = id == "0" && controllers.is_empty();
+        if !v2 && !controllers.split(',').any(|controller| controller == "cpu") {
+            continue;
+        }
+
+        let Some((root, mountpoint)) = mount(mounts, v2) else {
+            continue;
+        };
+        let relative = std::path::Path::new(path)
+            .strip_prefix(root)
+            .unwrap_or_else(|_| std::path::Path::new(""));
+
+        let mut dir = std::path::Path::new(mountpoint).join(relative);
+        loop {
+            let limit = if v2 {
+                limit_v2(&dir, &read)
+            } else {
+                limit_v1(&dir, &read)
+            };
+            if let Some(limit) = limit {
+                quota = Some(quota.map_or(limit, |quota| quota.min(limit)));
+            }
+            if dir == std::path::Path::new(mountpoint) || !dir.pop() {
+                break;
+            }
+        }
+    }
+
+    quota
+}
+
+#[cfg(target_os = "linux")]
+fn mount(mounts: &str, v2: bool) -> Option<(&str, &str)> {
+    mounts.lines().find_map(|line| {
+        let (fields, rest) = line.split_once(" - ")?;
+        let mut fields = fields.split(' ').skip(3);
+        let (root, mountpoint) = (fields.next()?, fields.next()?);
+
+        let mut rest = rest.split(' ');
+        let fstype = rest.next()?;
+        let options = rest.nth(1)?;
+
+        let matches = if v2 {
+            fstype == "cgroup2"
+        } else {
+            fstype == "cgroup" && options.split(',').any(|option| option == "cpu")
+        };
+        matches.then_some((root, mountpoint))
+    })
+}
+
+#[cfg(target_os = "linux")]
+#[allow(clippy::cast_precision_loss)]
+fn limit_v2(
+    dir: &std::path::Path,
+    read: impl Fn(&std::path::Path) -> Option<String>,
+) -> Option<f64> {
+    let max = read(&dir.join("cpu.max"))?;
+    let (quota, period) = max.trim().split_once(' ')?;
+    let quota = quota.parse::<u64>().ok()?;
+    let period = period.parse::<u64>().ok().filter(|period| *period > 0)?;
+    Some(quota as f64 / period as f64)
+}
+
+#[cfg(target_os = "linux")]
+#[allow(clippy::cast_precision_loss)]
+fn limit_v1(
+    dir: &std::path::Path,
+    read: impl Fn(&std::path::Path) -> Option<String>,
+) -> Option<f64> {
+    let quota = read(&dir.join("cpu.cfs_quota_us"))?
+        .trim()
+        .parse::<i64>()
+        .ok()?;
+    let period = read(&dir.join("cpu.cfs_period_us"))?
+        .trim()
+        .parse::<i64>()
+        .ok()?;
+    (quota > 0 && period > 0).then(|| quota as f64 / period as f64)
+}
+
+#[cfg(all(feature = "rt-affinity", target_os = "linux"))]
+pub(super) fn pin(cpu: usize) -> Result<(), std::io::Error> {
+    // SAFETY: cpu_set_t is a plain bit array for which all zeroes is the empty set.
+    let mut set = unsafe { std::mem::zeroed::<libc::cpu_set_t>() };
+    if cpu >= 8 * std::mem::size_of_val(&set) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("cpu {cpu} is outside of the affinity mask"),
+        ));
+    }
+    // SAFETY: cpu was checked to be within the bounds of the set.
+    unsafe { libc::CPU_SET(cpu, &mut set) };
+
+    // SAFETY: set is a valid cpu_set_t and the size passed is its exact size.
+    let result = unsafe { libc::sched_setaffinity(0, std::mem::size_of_val(&set), &raw const set) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    fn files(files: &[(&str, &str)]) -> impl Fn(&std::path::Path) -> Option<String> + use<> {
+        let files = files
+            .iter()
+            .map(|(path, content)| (std::path::PathBuf::from(path), (*content).to_owned()))
+            .collect::<std::collections::HashMap<_, _>>();
+        move |path| files.get(path).cloned()
+    }
+
+    const V1_MOUNTS: &str = "\
+25 30 0:22 / /sys/fs/cgroup ro,nosuid,nodev,noexec shared:9 - tmpfs tmpfs ro,mode=755
+32 25 0:28 / /sys/fs/cgroup/memory rw,nosuid,nodev,noexec,relatime shared:14 - cgroup cgroup rw,memory
+33 25 0:29 / /sys/fs/cgroup/cpu,cpuacct rw,nosuid,nodev,noexec,relatime shared:15 - cgroup cgroup rw,cpu,cpuacct
+";
+
+    const V2_MOUNTS: &str = "\
+22 28 0:20 / /proc rw,nosuid,nodev,noexec,relatime shared:12 - proc proc rw
+35 24 0:30 / /sys/fs/cgroup rw,nosuid,nodev,noexec,relatime shared:9 - cgroup2 cgroup2 rw,nsdelegate,memory_recursiveprot
+";
+
+    const MIXED_MOUNTS: &str = "\
+25 30 0:22 / /sys/fs/cgroup ro,nosuid,nodev,noexec shared:9 - tmpfs tmpfs ro,mode=755
+26 25 0:23 / /sys/fs/cgroup/unified rw,nosuid,nodev,noexec,relatime shared:10 - cgroup2 cgroup2 rw,nsdelegate
+33 25 0:29 / /sys/fs/cgroup/cpu,cpuacct rw,nosuid,nodev,noexec,relatime shared:15 - cgroup cgroup rw,cpu,cpuacct
+";
+
+    #[test]
+    fn affinity() {
+        let status = "Name:\tapp\nCpus_allowed:\tff\nCpus_allowed_list:\t0-3,8,10-11\n";
+        assert_eq!(super::affinity(status), Some(vec![0, 1, 2, 3, 8, 10, 11]));
+        assert_eq!(super::affinity("Cpus_allowed_list:\t5\n"), Some(vec![5]));
+    }
+
+    #[test]
+    fn affinity_invalid() {
+        assert_eq!(super::affinity("Name:\tapp\n"), None);
+        assert_eq!(super::affinity("Cpus_allowed_list:\t\n"), None);
+        assert_eq!(super::affinity("Cpus_allowed_list:\t0-x\n"), None);
+    }
+
+    #[test]
+    fn quota_v1() {
+        let cgroups = "12:cpu,cpuacct:/docker/abc\n11:memory:/docker/abc\n";
+        let read = files(&[
+            (
+                "/sys/fs/cgroup/cpu,cpuacct/docker/abc/cpu.cfs_quota_us",
+                "200000\n",
+            ),
+            (
+                "/sys/fs/cgroup/cpu,cpuacct/docker/abc/cpu.cfs_period_us",
+                "100000\n",
+            ),
+            ("/sys/fs/cgroup/cpu,cpuacct/cpu.cfs_quota_us", "-1\n"),
+            ("/sys/fs/cgroup/cpu,cpuacct/cpu.cfs_period_us", "100000\n"),
+        ]);
+        assert_eq!(super::quota(cgroups, V1_MOUNTS, read), Some(2.0));
+    }
+
+    #[test]
+    fn quota_v1_namespaced() {
+        let cgroups = "12:cpu,cpuacct:/docker/abc\n";
+        let mounts = "33 25 0:29 /docker/abc /sys/fs/cgroup/cpu rw,relatime - cgroup cgroup rw,cpu,cpuacct\n";
+        let read = files(&[
+            ("/sys/fs/cgroup/cpu/cpu.cfs_quota_us", "50000\n"),
+            ("/sys/fs/cgroup/cpu/cpu.cfs_period_us", "100000\n"),
+        ]);
+        assert_eq!(super::quota(cgroups, mounts, read), Some(0.5));
+    }
+
+    #[test]
+    fn quota_v1_unlimited() {
+        let cgroups = "12:cpu,cpuacct:/\n";
+        let read = files(&[
+            ("/sys/fs/cgroup/cpu,cpuacct/cpu.cfs_quota_us", "-1\n"),
+            ("/sys/fs/cgroup/cpu,cpuacct/cpu.cfs_period_us", "100000\n"),
+        ]);
+        assert_eq!(super::quota(cgroups, V1_MOUNTS, read), None);
+    }
+
+    #[test]
+    fn quota_v2() {
+        let cgroups = "0::/system.slice/app.service\n";
+        let read = files(&[
+            (
+                "/sys/fs/cgroup/system.slice/app.service/cpu.max",
+                "150000 100000\n",
+            ),
+            ("/sys/fs/cgroup/system.slice/cpu.max", "max 100000\n"),
+        ]);
+        assert_eq!(super::quota(cgroups, V2_MOUNTS, read), Some(1.5));
+    }
+
+    #[test]
+    fn quota_v2_nested() {
+        let cgroups = "0::/system.slice/app.service\n";
+        let read = files(&[
+            (
+                "/sys/fs/cgroup/system.slice/app.service/cpu.max",
+                "400000 100000\n",
+            ),
+            ("/sys/fs/cgroup/system.slice/cpu.max", "100000 100000\n"),
+        ]);
+        assert_eq!(super::quota(cgroups, V2_MOUNTS, read), Some(1.0));
+    }
+
+    #[test]
+    fn quota_v2_unlimited() {
+        let cgroups = "0::/\n";
+        let read = files(&[("/sys/fs/cgroup/cpu.max", "max 100000\n")]);
+        assert_eq!(super::quota(cgroups, V2_MOUNTS, read), None);
+    }
+
+    #[test]
+    fn quota_mixed() {
+        let cgroups = "4:cpu,cpuacct:/user.slice\n2:memory:/user.slice\n0::/user.slice\n";
+        let read = files(&[
+            (
+                "/sys/fs/cgroup/cpu,cpuacct/user.slice/cpu.cfs_quota_us",
+                "300000\n",
+            ),
+            (
+                "/sys/fs/cgroup/cpu,cpuacct/user.slice/cpu.cfs_period_us",
+                "100000\n",
+            ),
+        ]);
+        assert_eq!(super::quota(cgroups, MIXED_MOUNTS, &read), Some(3.0));
+
+        let read = files(&[
+            (
+                "/sys/fs/cgroup/cpu,cpuacct/user.slice/cpu.cfs_quota_us",
+                "300000\n",
+            ),
+            (
+                "/sys/fs/cgroup/cpu,cpuacct/user.slice/cpu.cfs_period_us",
+                "100000\n",
+            ),
+            (
+                "/sys/fs/cgroup/unified/user.slice/cpu.max",
+                "50000 100000\n",
+            ),
+        ]);
+        assert_eq!(super::quota(cgroups, MIXED_MOUNTS, read), Some(0.5));
+    }
+
+    #[cfg(feature = "rt-affinity")]
+    #[test]
+    fn pin_out_of_range() {
+        let err = super::pin(1 << 20).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn count() {
+        let cpus = super::Cpus {
+            allowed: Some(vec![0, 1, 2, 3]),
+            quota: Some(1.5),
+        };
+        assert_eq!(cpus.count(), 2);
+
+        let cpus = super::Cpus {
+            allowed: Some(vec![0, 1]),
+            quota: Some(8.0),
+        };
+        assert_eq!(cpus.count(), 2);
+
+        let cpus = super::Cpus {
+            allowed: Some(vec![0, 1]),
+            quota: Some(0.1),
+        };
+        assert_eq!(cpus.count(), 1);
+    }
+}
@@ -1,9 +1,31 @@
+#[cfg(feature = "rt-compute")]
+pub mod compute;
+#[cfg(feature = "rt-threads")]
+mod cpus;
+mod runtime;
+
 #[cfg(feature = "rt-shutdown")]
 pub mod shutdown;
 
+#[cfg(feature = "rt-compute")]
+pub use compute::{Compute, Pool};
+pub use runtime::Runtime;
+
 #[cfg(feature = "rt-shutdown")]
 pub use shutdown::Shutdown;
 
+#[cfg(all(feature = "rt-signal", unix))]
+pub mod signal;
+
+#[cfg(all(feature = "rt-signal", unix))]
+pub use signal::{Signal, Signals};
+
+#[cfg(feature = "rt-tasks")]
+pub mod task;
+
+#[cfg(feature = "rt-tasks")]
+pub use task::{Supervisor, Task};
+
 #[cfg(feature = "rt-threads")]
 pub mod threads;
 
@@ -14,28 +36,13 @@ pub use threads::Threads;
 #[error("Could not build runtime: {0}")]
 pub struct Error(#[from] tokio::io::Error);
 
-#[cfg(feature = "rt-threads")]
-#[must_use]
-pub fn runtime(threads: Threads) -> tokio::runtime::Builder {
-    #[cfg(feature = "log")]
-    tracing::info!(%threads, "Building tokio runtime");
-    match threads {
-        Threads::Single => tokio::runtime::Builder::new_current_thread(),
-        Threads::Auto => tokio::runtime::Builder::new_multi_thread(),
-        Threads::Multi(threads::Count(count)) => {
-            let mut rt = tokio::runtime::Builder::new_multi_thread();
-            rt.worker_threads(usize::from(count));
-            rt
-        }
-    }
-}
-
-#[cfg(not(feature = "rt-threads"))]
 #[must_use]
-pub fn runtime() -> tokio::runtime::Builder {
-    #[cfg(feature = "log")]
-    tracing::info!(threads = %"Single", "Building tokio runtime");
-    tokio::runtime::Builder::new_current_thread()
+pub fn runtime(#[cfg(feature = "rt-threads")] threads: Threads) -> tokio::runtime::Builder {
+    Runtime::new(
+        #[cfg(feature = "rt-threads")]
+        threads,
+    )
+    .builder()
 }
 
 pub fn block_on<F: std::future::Future>(
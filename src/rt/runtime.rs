@@ -0,0 +1,211 @@
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Runtime {
+    #[cfg(feature = "rt-threads")]
+    threads: super::Threads,
+    name: String,
+    stack_size: Option<usize>,
+    max_blocking_threads: Option<usize>,
+    blocking_keep_alive: Option<std::time::Duration>,
+    event_interval: Option<u32>,
+    #[cfg(feature = "rt-affinity")]
+    pin: bool,
+}
+
+impl Runtime {
+    #[must_use]
+    pub fn new(#[cfg(feature = "rt-threads")] threads: super::Threads) -> Self {
+        Self {
+            #[cfg(feature = "rt-threads")]
+            threads,
+            name: String::from("worker"),
+            stack_size: None,
+            max_blocking_threads: None,
+            blocking_keep_alive: None,
+            event_interval: None,
+            #[cfg(feature = "rt-affinity")]
+            pin: false,
+        }
+    }
+
+    #[must_use]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    #[must_use]
+    pub fn stack_size(mut self, stack_size: usize) -> Self {
+        self.stack_size = Some(stack_size);
+        self
+    }
+
+    #[must_use]
+    pub fn max_blocking_threads(mut self, max_blocking_threads: usize) -> Self {
+        self.max_blocking_threads = Some(max_blocking_threads);
+        self
+    }
+
+    #[must_use]
+    pub fn blocking_keep_alive(mut self, blocking_keep_alive: std::time::Duration) -> Self {
+        self.blocking_keep_alive = Some(blocking_keep_alive);
+        self
+    }
+
+    #[must_use]
+    pub fn event_interval(mut self, event_interval: u32) -> Self {
+        self.event_interval = Some(event_interval);
+        self
+    }
+
+    #[cfg(feature = "rt-affinity")]
+    #[must_use]
+    pub fn pin(mut self, pin: bool) -> Self {
+        self.pin = pin;
+        self
+    }
+
+    #[must_use]
+    pub fn builder(&self) -> tokio::runtime::Builder {
+        #[cfg(feature = "rt-threads")]
+        let threads = self.threads;
+        #[cfg(all(feature = "log", not(feature = "rt-threads")))]
+        let threads = "Single";
+
+        #[cfg(feature = "log")]
+        tracing::info!(
+            %threads,
+            name = %self.name,
+            stack_size = ?self.stack_size,
+            max_blocking_threads = ?self.max_blocking_threads,
+            blocking_keep_alive = ?self.blocking_keep_alive,
+            event_interval = ?self.event_interval,
+            "Building tokio runtime"
+        );
+
+        #[cfg(feature = "rt-threads")]
+        let mut rt = match threads {
+            super::Threads::Single => tokio::runtime::Builder::new_current_thread(),
+            super::Threads::Multi(super::threads::Count(count)) => {
+                let mut rt = tokio::runtime::Builder::new_multi_thread();
+                rt.worker_threads(count);
+                rt
+            }
+            threads => {
+                let cpus = super::cpus::Cpus::detect();
+                let count = threads.derive(cpus.count());
+
+                #[cfg(feature = "log")]
+                tracing::info!(
+                    count,
+                    cpus = ?cpus.allowed,
+                    quota = ?cpus.quota,
+                    "Derived worker threads"
+                );
+
+                let mut rt = tokio::runtime::Builder::new_multi_thread();
+                rt.worker_threads(count);
+                rt
+            }
+        };
+        #[cfg(not(feature = "rt-threads"))]
+        let mut rt = tokio::runtime::Builder::new_current_thread();
+
+        let name = self.name.clone();
+        let id = std::sync::atomic::AtomicUsize::new(0);
+        rt.thread_name_fn(move || {
+            let id = id.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            format!("{name}-{id}")
+        });
+
+        if let Some(stack_size) = self.stack_size {
+            rt.thread_stack_size(stack_size);
+        }
+        if let Some(max_blocking_threads) = self.max_blocking_threads {
+            rt.max_blocking_threads(max_blocking_threads);
+        }
+        if let Some(blocking_keep_alive) = self.blocking_keep_alive {
+            rt.thread_keep_alive(blocking_keep_alive);
+        }
+        if let Some(event_interval) = self.event_interval {
+            rt.event_interval(event_interval);
+        }
+
+        #[cfg(feature = "log")]
+        rt.on_thread_start(|| {
+            tracing::debug!(thread = std::thread::current().name(), "Thread started");
+        })
+        .on_thread_stop(|| {
+            tracing::debug!(thread = std::thread::current().name(), "Thread stopped");
+        });
+
+        #[cfg(feature = "rt-affinity")]
+        if self.pin && threads != super::Threads::Single {
+            let cpus = super::cpus::Cpus::detect().allowed.unwrap_or_else(|| {
+                (0..std::thread::available_parallelism().map_or(1, std::num::NonZero::get))
+                    .collect()
+            });
+            let next = std::sync::atomic::AtomicUsize::new(0);
+            rt.on_thread_park(move || pin(&next, &cpus));
+        }
+
+        rt
+    }
+
+    pub fn build(&self) -> Result<tokio::runtime::Runtime, super::Error> {
+        Ok(self.builder().enable_all().build()?)
+    }
+}
+
+impl Default for Runtime {
+    fn default() -> Self {
+        Self::new(
+            #[cfg(feature = "rt-threads")]
+            super::Threads::Auto,
+        )
+    }
+}
+
+#[cfg(feature = "rt-threads")]
+impl From<super::Threads> for Runtime {
+    fn from(value: super::Threads) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(feature = "rt-affinity")]
+fn pin(next: &std::sync::atomic::AtomicUsize, cpus: &[usize]) {
+    thread_local! {
+        static PINNED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    }
+
+    if PINNED.replace(true) {
+        return;
+    }
+    let cpu = cpus[next.fetch_add(1, std::sync::atomic::Ordering::Relaxed) % cpus.len()];
+
+    #[cfg(target_os = "linux")]
+    let result = super::cpus::pin(cpu);
+    #[cfg(not(target_os = "linux"))]
+    let result = Err::<(), _>(std::io::Error::from(std::io::ErrorKind::Unsupported));
+
+    #[cfg(feature = "log")]
+    match result {
+        Ok(()) => {
+            tracing::debug!(
+                thread = std::thread::current().name(),
+                cpu,
+                "Pinned worker thread"
+            );
+        }
+        Err(err) => {
+            tracing::warn!(
+                thread = std::thread::current().name(),
+                cpu,
+                error = %err,
+                "Could not pin worker thread"
+            );
+        }
+    }
+    #[cfg(not(feature = "log"))]
+    let _ = result;
+}
@@ -4,6 +4,113 @@ pub use inner::Shutdown;
 #[error("Could not hook on shutdown signal: {0}")]
 pub struct Error(#[from] tokio::io::Error);
 
+static TOKEN: std::sync::OnceLock<Token> = std::sync::OnceLock::new();
+static LISTENING: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+#[must_use]
+pub fn token() -> Token {
+    TOKEN.get_or_init(Token::new).clone()
+}
+
+pub fn listen() -> Result<Token, Error> {
+    let token = token();
+
+    if !token.is_cancelled() && !LISTENING.load(std::sync::atomic::Ordering::Acquire) {
+        let signal = Shutdown::new()?;
+        if LISTENING.swap(true, std::sync::atomic::Ordering::AcqRel) {
+            return Ok(token);
+        }
+
+        let listener = token.clone();
+        tokio::spawn(async move {
+            let _listening = Listening;
+            tokio::select! {
+                () = signal => listener.cancel(),
+                () = listener.cancelled() => {}
+            }
+        });
+    }
+
+    Ok(token)
+}
+
+struct Listening;
+
+impl Drop for Listening {
+    fn drop(&mut self) {
+        LISTENING.store(false, std::sync::atomic::Ordering::Release);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Token(std::sync::Arc<tokio::sync::watch::Sender<bool>>);
+
+impl Token {
+    #[must_use]
+    pub fn new() -> Self {
+        Self(std::sync::Arc::new(tokio::sync::watch::Sender::new(false)))
+    }
+
+    pub fn cancel(&self) {
+        let flipped = self.0.send_if_modified(|cancelled| {
+            if *cancelled {
+                false
+            } else {
+                #[cfg(feature = "log")]
+                tracing::info!("Shutdown started");
+
+                *cancelled = true;
+                true
+            }
+        });
+
+        #[cfg(all(feature = "systemd", unix))]
+        #[cfg_attr(not(feature = "log"), allow(unused_variables))]
+        if flipped
+            && TOKEN
+                .get()
+                .is_some_and(|token| std::sync::Arc::ptr_eq(&token.0, &self.0))
+            && let Err(err) = crate::systemd::stopping()
+        {
+            #[cfg(feature = "log")]
+            tracing::warn!(error = %err, "Could not notify systemd of shutdown");
+        }
+        #[cfg(not(all(feature = "systemd", unix)))]
+        let _ = flipped;
+    }
+
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        *self.0.borrow()
+    }
+
+    pub fn cancelled(&self) -> impl std::future::Future<Output = ()> + Send + 'static {
+        let token = self.clone();
+        async move {
+            let mut receiver = token.0.subscribe();
+            drop(receiver.wait_for(|cancelled| *cancelled).await);
+        }
+    }
+}
+
+impl Default for Token {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(any(feature = "server-h1", feature = "server-h2"))]
+impl<S: Send + Sync> axum::extract::FromRequestParts<S> for Token {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(
+        _: &mut axum::http::request::Parts,
+        _: &S,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(token())
+    }
+}
+
 #[cfg(unix)]
 mod inner {
     pub struct Shutdown(tokio::signal::unix::Signal, tokio::signal::unix::Signal);
@@ -25,12 +132,14 @@ mod inner {
             ctx: &mut std::task::Context<'_>,
         ) -> std::task::Poll<Self::Output> {
             match self.0.poll_recv(ctx) {
-                std::task::Poll::Ready(_) => std::task::Poll::Ready(()),
+                std::task::Poll::Ready(_) => {}
                 std::task::Poll::Pending => match self.1.poll_recv(ctx) {
-                    std::task::Poll::Ready(_) => std::task::Poll::Ready(()),
-                    std::task::Poll::Pending => std::task::Poll::Pending,
+                    std::task::Poll::Ready(_) => {}
+                    std::task::Poll::Pending => return std::task::Poll::Pending,
                 },
             }
+
+            std::task::Poll::Ready(())
         }
     }
 }
@@ -0,0 +1,114 @@
+#[derive(Debug, thiserror::Error)]
+#[error("Could not hook on {0} signal: {1}")]
+pub struct Error(Signal, #[source] tokio::io::Error);
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Signal {
+    Hangup,
+    Interrupt,
+    Quit,
+    Terminate,
+    User1,
+    User2,
+}
+
+impl Signal {
+    fn kind(self) -> tokio::signal::unix::SignalKind {
+        match self {
+            Self::Hangup => tokio::signal::unix::SignalKind::hangup(),
+            Self::Interrupt => tokio::signal::unix::SignalKind::interrupt(),
+            Self::Quit => tokio::signal::unix::SignalKind::quit(),
+            Self::Terminate => tokio::signal::unix::SignalKind::terminate(),
+            Self::User1 => tokio::signal::unix::SignalKind::user_defined1(),
+            Self::User2 => tokio::signal::unix::SignalKind::user_defined2(),
+        }
+    }
+
+    #[cfg(feature = "rt-shutdown")]
+    fn is_shutdown(self) -> bool {
+        matches!(self, Self::Interrupt | Self::Terminate)
+    }
+}
+
+impl std::fmt::Display for Signal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Hangup => f.write_str("SIGHUP"),
+            Self::Interrupt => f.write_str("SIGINT"),
+            Self::Quit => f.write_str("SIGQUIT"),
+            Self::Terminate => f.write_str("SIGTERM"),
+            Self::User1 => f.write_str("SIGUSR1"),
+            Self::User2 => f.write_str("SIGUSR2"),
+        }
+    }
+}
+
+type Handler = Box<dyn Fn() + Send + Sync>;
+
+pub struct Signals(std::collections::HashMap<Signal, Vec<Handler>>);
+
+impl Signals {
+    #[must_use]
+    pub fn new() -> Self {
+        #[cfg(feature = "rt-shutdown")]
+        let handlers = [Signal::Interrupt, Signal::Terminate]
+            .into_iter()
+            .map(|signal| (signal, Vec::new()))
+            .collect();
+        #[cfg(not(feature = "rt-shutdown"))]
+        let handlers = std::collections::HashMap::new();
+
+        Self(handlers)
+    }
+
+    #[must_use]
+    pub fn on<F: Fn() + Send + Sync + 'static>(mut self, signal: Signal, handler: F) -> Self {
+        self.0.entry(signal).or_default().push(Box::new(handler));
+        self
+    }
+
+    pub fn listen(self) -> Result<(), Error> {
+        let streams = self
+            .0
+            .into_iter()
+            .map(|(signal, handlers)| {
+                tokio::signal::unix::signal(signal.kind())
+                    .map(|stream| (signal, stream, handlers))
+                    .map_err(|err| Error(signal, err))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        #[cfg_attr(
+            not(any(feature = "log", feature = "rt-shutdown")),
+            allow(unused_variables)
+        )]
+        for (signal, mut stream, handlers) in streams {
+            #[cfg(feature = "log")]
+            tracing::debug!(%signal, handlers = handlers.len(), "Listening to signal");
+
+            tokio::spawn(async move {
+                while stream.recv().await.is_some() {
+                    #[cfg(feature = "log")]
+                    tracing::info!(%signal, "Received signal");
+
+                    for handler in &handlers {
+                        handler();
+                    }
+
+                    #[cfg(feature = "rt-shutdown")]
+                    if signal.is_shutdown() {
+                        crate::rt::shutdown::token().cancel();
+                    }
+                }
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Signals {
+    fn default() -> Self {
+        Self::new()
+    }
+}
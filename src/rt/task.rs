@@ -0,0 +1,226 @@
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum Restart {
+    #[default]
+    Never,
+    OnPanic,
+    Always,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Backoff {
+    pub initial: std::time::Duration,
+    pub max: std::time::Duration,
+    pub factor: u32,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: std::time::Duration::from_millis(100),
+            max: std::time::Duration::from_secs(30),
+            factor: 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Task {
+    name: String,
+    restart: Restart,
+    backoff: Backoff,
+}
+
+impl Task {
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            restart: Restart::default(),
+            backoff: Backoff::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn restart(mut self, restart: Restart) -> Self {
+        self.restart = restart;
+        self
+    }
+
+    #[must_use]
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    async fn supervise<F, Fut>(self, factory: F, token: super::shutdown::Token)
+    where
+        F: Fn(super::shutdown::Token) -> Fut,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let mut delay = self.backoff.initial;
+        loop {
+            #[cfg(feature = "log")]
+            tracing::info!(task = %self.name, "Task started");
+
+            let start = tokio::time::Instant::now();
+            let handle = tokio::spawn(factory(token.clone()));
+            let _abort = Abort(handle.abort_handle());
+
+            let panicked = match handle.await {
+                Ok(()) => {
+                    #[cfg(feature = "log")]
+                    tracing::info!(task = %self.name, duration = ?start.elapsed(), "Task exited");
+                    false
+                }
+                Err(err) if err.is_panic() => {
+                    #[cfg(feature = "log")]
+                    {
+                        let payload = err.into_panic();
+                        let panic = crate::unwind::message(&*payload);
+                        tracing::error!(task = %self.name, panic, duration = ?start.elapsed(), "Task panicked");
+                    }
+                    true
+                }
+                Err(_) => return,
+            };
+
+            let restart = match self.restart {
+                Restart::Never => false,
+                Restart::OnPanic => panicked,
+                Restart::Always => true,
+            };
+            if !restart || token.is_cancelled() {
+                return;
+            }
+
+            if start.elapsed() >= self.backoff.max {
+                delay = self.backoff.initial;
+            }
+
+            #[cfg(feature = "log")]
+            tracing::info!(task = %self.name, ?delay, "Restarting task");
+
+            tokio::select! {
+                () = tokio::time::sleep(delay) => {}
+                () = token.cancelled() => return,
+            }
+
+            delay = delay
+                .saturating_mul(self.backoff.factor)
+                .min(self.backoff.max);
+        }
+    }
+}
+
+impl From<&str> for Task {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for Task {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+struct Abort(tokio::task::AbortHandle);
+
+impl Drop for Abort {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+#[derive(Debug)]
+pub struct Supervisor {
+    token: super::shutdown::Token,
+    tasks: tokio::task::JoinSet<()>,
+    names: std::collections::HashMap<tokio::task::Id, String>,
+}
+
+impl Supervisor {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_token(super::shutdown::token())
+    }
+
+    #[must_use]
+    pub fn with_token(token: super::shutdown::Token) -> Self {
+        Self {
+            token,
+            tasks: tokio::task::JoinSet::new(),
+            names: std::collections::HashMap::new(),
+        }
+    }
+
+    pub fn spawn<F, Fut>(&mut self, task: impl Into<Task>, factory: F)
+    where
+        F: Fn(super::shutdown::Token) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let task = task.into();
+        let name = task.name.clone();
+        let id = self
+            .tasks
+            .spawn(task.supervise(factory, self.token.clone()))
+            .id();
+        self.names.insert(id, name);
+    }
+
+    pub async fn join(
+        mut self,
+        deadline: Option<std::time::Duration>,
+    ) -> Result<(), super::shutdown::Error> {
+        let token = super::shutdown::listen()?;
+
+        tokio::select! {
+            () = reap(&mut self.tasks, &mut self.names) => return Ok(()),
+            () = token.cancelled() => {}
+            () = self.token.cancelled() => {}
+        }
+
+        #[cfg(feature = "log")]
+        tracing::info!(tasks = self.tasks.len(), "Waiting for tasks to finish");
+
+        let drained = if let Some(deadline) = deadline {
+            tokio::time::timeout(deadline, reap(&mut self.tasks, &mut self.names))
+                .await
+                .is_ok()
+        } else {
+            reap(&mut self.tasks, &mut self.names).await;
+            true
+        };
+
+        if !drained {
+            #[cfg(feature = "log")]
+            tracing::warn!(
+                tasks = ?self.names.values().collect::<Vec<_>>(),
+                "Aborting remaining tasks"
+            );
+
+            self.tasks.shutdown().await;
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Supervisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+async fn reap(
+    tasks: &mut tokio::task::JoinSet<()>,
+    names: &mut std::collections::HashMap<tokio::task::Id, String>,
+) {
+    while let Some(result) = tasks.join_next_with_id().await {
+        let id = match result {
+            Ok((id, ())) => id,
+            Err(err) => err.id(),
+        };
+        names.remove(&id);
+    }
+}
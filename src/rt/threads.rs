@@ -1,12 +1,22 @@
 #[derive(Debug, thiserror::Error)]
-#[error("Expected `auto` or a value in the [1..=255] range")]
-pub struct Error;
+pub enum Error {
+    #[error(
+        "Invalid thread count `{0}`: expected `auto`, `auto-N`, `auto+N`, `N%` or a positive count"
+    )]
+    Invalid(String),
+    #[error(
+        "Invalid thread count `{input}` in environment variable {name}: expected `auto`, `auto-N`, `auto+N`, `N%` or a positive count"
+    )]
+    Env { name: String, input: String },
+}
 
 #[derive(Copy, Clone, Eq, PartialEq)]
 pub enum Threads {
     Single,
     Auto,
     Multi(Count),
+    Percent(Percent),
+    Relative(Relative),
 }
 
 impl Threads {
@@ -15,13 +25,59 @@ impl Threads {
         Self::Auto
     }
 
-    pub fn count(count: u8) -> Result<Self, Error> {
-        if count == 0 {
-            Err(Error)
-        } else if count == 1 {
-            Ok(Self::Single)
-        } else {
-            Ok(Self::Multi(Count(count)))
+    pub fn count(count: usize) -> Result<Self, Error> {
+        match count {
+            0 => Err(Error::Invalid(count.to_string())),
+            1 => Ok(Self::Single),
+            _ => Ok(Self::Multi(Count(count))),
+        }
+    }
+
+    pub fn percent(percent: u16) -> Result<Self, Error> {
+        match percent {
+            0 => Err(Error::Invalid(format!("{percent}%"))),
+            _ => Ok(Self::Percent(Percent(percent))),
+        }
+    }
+
+    #[must_use]
+    pub fn relative(delta: isize) -> Self {
+        match delta {
+            0 => Self::Auto,
+            _ => Self::Relative(Relative(delta)),
+        }
+    }
+
+    pub fn from_env(name: &str) -> Result<Option<Self>, Error> {
+        match std::env::var(name) {
+            Ok(input) => parse(&input).map(Some).map_err(|_| Error::Env {
+                name: name.to_owned(),
+                input,
+            }),
+            Err(std::env::VarError::NotPresent) => Ok(None),
+            Err(std::env::VarError::NotUnicode(input)) => Err(Error::Env {
+                name: name.to_owned(),
+                input: input.to_string_lossy().into_owned(),
+            }),
+        }
+    }
+
+    #[must_use]
+    pub fn workers(self) -> usize {
+        match self {
+            Self::Single => 1,
+            Self::Multi(Count(count)) => count,
+            _ => self.derive(super::cpus::Cpus::detect().count()),
+        }
+    }
+
+    pub(super) fn derive(self, cpus: usize) -> usize {
+        match self {
+            Self::Single => 1,
+            Self::Multi(Count(count)) => count,
+            Self::Auto => cpus,
+            Self::Percent(Percent(percent)) => (cpus * usize::from(percent) / 100).max(1),
+            Self::Relative(Relative(delta)) => cpus.saturating_add_signed(delta).max(1),
         }
     }
 }
@@ -32,6 +88,8 @@ impl std::fmt::Display for Threads {
             Threads::Single => f.write_str("Single"),
             Threads::Auto => f.write_str("Auto"),
             Threads::Multi(count) => write!(f, "Multi({count})"),
+            Threads::Percent(percent) => write!(f, "Percent({percent})"),
+            Threads::Relative(delta) => write!(f, "Relative({delta})"),
         }
     }
 }
@@ -42,25 +100,119 @@ impl std::fmt::Debug for Threads {
     }
 }
 
+impl std::str::FromStr for Threads {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s)
+    }
+}
+
 pub fn parse(input: &str) -> Result<Threads, Error> {
-    if input == "auto" {
+    let invalid = || Error::Invalid(input.to_owned());
+    let trimmed = input.trim();
+
+    if trimmed.eq_ignore_ascii_case("auto") {
         Ok(Threads::Auto)
+    } else if let Some(percent) = trimmed.strip_suffix('%') {
+        percent
+            .trim_end()
+            .parse()
+            .map_err(|_| invalid())
+            .and_then(|percent| Threads::percent(percent).map_err(|_| invalid()))
+    } else if let Some(delta) = trimmed
+        .get(..4)
+        .filter(|auto| auto.eq_ignore_ascii_case("auto"))
+        .map(|_| &trimmed[4..])
+    {
+        let (sign, delta) = match delta.split_at_checked(1) {
+            Some(("+", delta)) => (1, delta),
+            Some(("-", delta)) => (-1, delta),
+            _ => return Err(invalid()),
+        };
+        let delta = delta
+            .parse::<usize>()
+            .ok()
+            .and_then(|delta| isize::try_from(delta).ok())
+            .ok_or_else(invalid)?;
+        Ok(Threads::relative(sign * delta))
     } else {
-        input.parse().map_err(|_| Error).and_then(Threads::count)
+        trimmed
+            .parse()
+            .map_err(|_| invalid())
+            .and_then(|count| Threads::count(count).map_err(|_| invalid()))
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Threads {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Threads::Single => serializer.serialize_u64(1),
+            Threads::Auto => serializer.serialize_str("auto"),
+            Threads::Multi(Count(count)) => serializer.serialize_u64(*count as u64),
+            Threads::Percent(Percent(percent)) => {
+                serializer.collect_str(&format_args!("{percent}%"))
+            }
+            Threads::Relative(Relative(delta)) => {
+                serializer.collect_str(&format_args!("auto{delta:+}"))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Threads {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl serde::de::Visitor<'_> for Visitor {
+            type Value = Threads;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("`auto`, `auto-N`, `auto+N`, `N%` or a positive thread count")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                parse(value).map_err(E::custom)
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                usize::try_from(value)
+                    .map_err(|_| Error::Invalid(value.to_string()))
+                    .and_then(Threads::count)
+                    .map_err(E::custom)
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Self::Value, E> {
+                usize::try_from(value)
+                    .map_err(|_| Error::Invalid(value.to_string()))
+                    .and_then(Threads::count)
+                    .map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct Count(pub(super) usize);
+
 #[derive(Copy, Clone, Eq, PartialEq)]
-pub struct Count(pub(super) u8);
+pub struct Percent(pub(super) u16);
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct Relative(pub(super) isize);
 
 macro_rules! impl_fmt {
-    ($fmt: ident, $($rest: ident),*) => {
-        impl_fmt!($fmt);
-        impl_fmt!($($rest),*);
+    ($ty: ident, $fmt: ident, $($rest: ident),*) => {
+        impl_fmt!($ty, $fmt);
+        impl_fmt!($ty, $($rest),*);
     };
 
-    ($fmt: ident) => {
-        impl std::fmt::$fmt for Count {
+    ($ty: ident, $fmt: ident) => {
+        impl std::fmt::$fmt for $ty {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 self.0.fmt(f)
             }
@@ -69,5 +221,152 @@ macro_rules! impl_fmt {
 }
 
 impl_fmt!(
-    Display, Debug, Octal, Binary, UpperHex, LowerHex, UpperExp, LowerExp
+    Count, Display, Debug, Octal, Binary, UpperHex, LowerHex, UpperExp, LowerExp
 );
+impl_fmt!(Percent, Display, Debug);
+impl_fmt!(Relative, Display, Debug);
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn parse_auto() {
+        for input in ["auto", "AUTO", " Auto ", "auto-0", "auto+0", "AUTO-0"] {
+            assert_eq!(
+                super::parse(input).unwrap(),
+                super::Threads::Auto,
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_relative() {
+        assert_eq!(
+            super::parse("auto-2").unwrap(),
+            super::Threads::Relative(super::Relative(-2))
+        );
+        assert_eq!(
+            super::parse(" AUTO+3 ").unwrap(),
+            super::Threads::Relative(super::Relative(3))
+        );
+    }
+
+    #[test]
+    fn parse_percent() {
+        assert_eq!(
+            super::parse("50%").unwrap(),
+            super::Threads::Percent(super::Percent(50))
+        );
+        assert_eq!(
+            super::parse(" 50 % ").unwrap(),
+            super::Threads::Percent(super::Percent(50))
+        );
+        assert_eq!(
+            super::parse("150%").unwrap(),
+            super::Threads::Percent(super::Percent(150))
+        );
+    }
+
+    #[test]
+    fn parse_count() {
+        assert_eq!(super::parse("1").unwrap(), super::Threads::Single);
+        assert_eq!(
+            super::parse(" 4 ").unwrap(),
+            super::Threads::Multi(super::Count(4))
+        );
+        assert_eq!(
+            super::parse("1024").unwrap(),
+            super::Threads::Multi(super::Count(1024))
+        );
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for input in [
+            "", " ", "0", "-1", "0%", "%", "-5%", "auto-", "auto+", "auto2", "auto--1", "autox",
+            "many",
+        ] {
+            assert!(super::parse(input).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn constructors() {
+        assert_eq!(super::Threads::count(1).unwrap(), super::Threads::Single);
+        assert!(super::Threads::count(0).is_err());
+        assert_eq!(
+            super::Threads::percent(50).unwrap(),
+            super::Threads::Percent(super::Percent(50))
+        );
+        assert!(super::Threads::percent(0).is_err());
+        assert_eq!(
+            super::Threads::relative(-2),
+            super::Threads::Relative(super::Relative(-2))
+        );
+        assert_eq!(super::Threads::relative(0), super::Threads::Auto);
+    }
+
+    #[test]
+    fn derive() {
+        assert_eq!(super::Threads::Auto.derive(8), 8);
+        assert_eq!(super::Threads::Percent(super::Percent(50)).derive(8), 4);
+        assert_eq!(super::Threads::Percent(super::Percent(1)).derive(8), 1);
+        assert_eq!(super::Threads::Relative(super::Relative(-2)).derive(8), 6);
+        assert_eq!(super::Threads::Relative(super::Relative(-16)).derive(8), 1);
+        assert_eq!(super::Threads::Relative(super::Relative(4)).derive(8), 12);
+    }
+
+    #[test]
+    fn format_count() {
+        let count = super::Count(1500);
+        assert_eq!(format!("{count}"), "1500");
+        assert_eq!(format!("{count:x}"), "5dc");
+        assert_eq!(format!("{count:e}"), "1.5e3");
+        assert_eq!(format!("{count:E}"), "1.5E3");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        for threads in [
+            super::Threads::Single,
+            super::Threads::Auto,
+            super::Threads::Multi(super::Count(12)),
+            super::Threads::Percent(super::Percent(75)),
+            super::Threads::Relative(super::Relative(-1)),
+            super::Threads::Relative(super::Relative(2)),
+        ] {
+            let value = serde_json::to_value(threads).unwrap();
+            assert_eq!(
+                serde_json::from_value::<super::Threads>(value).unwrap(),
+                threads
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_values() {
+        let parse = |value| serde_json::from_value::<super::Threads>(value);
+
+        assert_eq!(
+            serde_json::to_value(super::Threads::Relative(super::Relative(-1))).unwrap(),
+            "auto-1"
+        );
+        assert_eq!(
+            serde_json::to_value(super::Threads::Percent(super::Percent(75))).unwrap(),
+            "75%"
+        );
+        assert_eq!(
+            parse(serde_json::json!(4)).unwrap(),
+            super::Threads::Multi(super::Count(4))
+        );
+        assert_eq!(
+            parse(serde_json::json!("auto")).unwrap(),
+            super::Threads::Auto
+        );
+        assert!(parse(serde_json::json!(0)).is_err());
+        assert!(parse(serde_json::json!(-1)).is_err());
+        assert!(parse(serde_json::json!("0%")).is_err());
+    }
+}
@@ -0,0 +1,207 @@
+type Check = std::sync::Arc<
+    dyn Fn() -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<(), String>> + Send>>
+        + Send
+        + Sync,
+>;
+
+#[derive(Clone)]
+pub struct Admin {
+    listener: super::Listener,
+    timeout: std::time::Duration,
+    checks: Vec<(&'static str, Check)>,
+    info: Vec<(&'static str, String)>,
+    draining: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl Admin {
+    #[must_use]
+    pub fn new(listener: impl Into<super::Listener>) -> Self {
+        Self {
+            listener: listener.into(),
+            timeout: std::time::Duration::from_secs(5),
+            checks: Vec::new(),
+            info: Vec::new(),
+            draining: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
+        }
+    }
+
+    #[must_use]
+    pub fn check<F, Fut, E>(mut self, name: &'static str, check: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<(), E>> + Send + 'static,
+        E: std::fmt::Display,
+    {
+        let check = std::sync::Arc::new(check);
+        self.checks.push((
+            name,
+            std::sync::Arc::new(move || {
+                let future = check();
+                Box::pin(async move { future.await.map_err(|err| err.to_string()) })
+            }),
+        ));
+        self
+    }
+
+    #[must_use]
+    pub fn info(mut self, key: &'static str, value: impl Into<String>) -> Self {
+        self.info.push((key, value.into()));
+        self
+    }
+
+    #[must_use]
+    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub(super) fn drain(&self) {
+        if !self
+            .draining
+            .swap(true, std::sync::atomic::Ordering::AcqRel)
+        {
+            #[cfg(feature = "log")]
+            tracing::info!(addr = %self.listener, "Reporting not ready");
+        }
+    }
+
+    fn router(&self) -> axum::Router {
+        let ready = self.clone();
+        let info = self
+            .info
+            .iter()
+            .map(|(key, value)| [key, ": ", value.as_str(), "\n"].concat())
+            .collect::<String>();
+
+        axum::Router::new()
+            .route("/healthz", axum::routing::get(|| async { "ok\n" }))
+            .route("/livez", axum::routing::get(|| async { "ok\n" }))
+            .route(
+                "/readyz",
+                axum::routing::get(move || {
+                    let ready = ready.clone();
+                    async move { ready.ready().await }
+                }),
+            )
+            .route("/version", axum::routing::get(move || async move { info }))
+    }
+
+    async fn ready(&self) -> (axum::http::StatusCode, String) {
+        #[cfg(feature = "rt-shutdown")]
+        let draining = self.draining.load(std::sync::atomic::Ordering::Acquire)
+            || crate::rt::shutdown::token().is_cancelled();
+        #[cfg(not(feature = "rt-shutdown"))]
+        let draining = self.draining.load(std::sync::atomic::Ordering::Acquire);
+
+        if draining {
+            return (
+                axum::http::StatusCode::SERVICE_UNAVAILABLE,
+                String::from("shutting down\n"),
+            );
+        }
+
+        let mut checks = tokio::task::JoinSet::new();
+        for (index, (_, check)) in self.checks.iter().enumerate() {
+            let check = check();
+            let timeout = self.timeout;
+            checks.spawn(async move {
+                let result = tokio::time::timeout(timeout, check)
+                    .await
+                    .unwrap_or_else(|_| Err(format!("timed out after {timeout:?}")));
+                (index, result)
+            });
+        }
+
+        let mut results = vec![Ok(()); self.checks.len()];
+        while let Some(result) = checks.join_next().await {
+            match result {
+                Ok((index, result)) => results[index] = result,
+                Err(err) => {
+                    #[cfg(feature = "log")]
+                    tracing::error!(error = %err, "Readiness check panicked");
+                    #[cfg(not(feature = "log"))]
+                    drop(err);
+
+                    return (
+                        axum::http::StatusCode::SERVICE_UNAVAILABLE,
+                        String::from("not ready\n"),
+                    );
+                }
+            }
+        }
+
+        let ready = results.iter().all(Result::is_ok);
+        let mut body = String::from(if ready { "ready\n" } else { "not ready\n" });
+        for ((name, _), result) in self.checks.iter().zip(results) {
+            match result {
+                Ok(()) => body.push_str(&[name, ": ok\n"].concat()),
+                Err(err) => {
+                    #[cfg(feature = "log")]
+                    tracing::warn!(check = name, error = %err, "Readiness check failed");
+
+                    body.push_str(&[name, ": ", err.as_str(), "\n"].concat());
+                }
+            }
+        }
+
+        if ready {
+            (axum::http::StatusCode::OK, body)
+        } else {
+            (axum::http::StatusCode::SERVICE_UNAVAILABLE, body)
+        }
+    }
+}
+
+impl std::fmt::Debug for Admin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Admin")
+            .field("listener", &self.listener)
+            .field("timeout", &self.timeout)
+            .field(
+                "checks",
+                &self.checks.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            )
+            .field("info", &self.info)
+            .finish_non_exhaustive()
+    }
+}
+
+pub(super) struct Running(Option<Server>);
+
+struct Server {
+    stop: tokio::sync::watch::Sender<bool>,
+    server: tokio::task::JoinHandle<Result<(), super::Error>>,
+}
+
+impl Running {
+    pub(super) fn start(admin: Option<&Admin>) -> Result<Self, super::Error> {
+        let Some(admin) = admin else {
+            return Ok(Self(None));
+        };
+
+        #[cfg(feature = "log")]
+        tracing::info!(addr = %admin.listener, "Starting admin server");
+
+        let listener = admin.listener.bind(&super::Socket::default())?;
+        let stop = tokio::sync::watch::Sender::new(false);
+        let server = tokio::spawn(listener.serve(
+            admin.router(),
+            super::triggered(stop.subscribe()),
+            super::Config::default(),
+            #[cfg(feature = "log")]
+            std::time::Instant::now(),
+        ));
+
+        Ok(Self(Some(Server { stop, server })))
+    }
+
+    pub(super) async fn stop(self, result: Result<(), super::Error>) -> Result<(), super::Error> {
+        let Some(Server { stop, server }) = self.0 else {
+            return result;
+        };
+
+        stop.send_replace(true);
+        let admin = server.await.map_err(super::Error::TaskJoin).and_then(|r| r);
+        result.and(admin)
+    }
+}
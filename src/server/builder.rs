@@ -0,0 +1,262 @@
+pub struct Builder {
+    router: super::Router,
+    config: super::Config,
+    layers: Layers,
+}
+
+struct Layers {
+    timeout: Option<super::Timeout>,
+    body_limit: Option<usize>,
+    #[cfg(feature = "server-compression")]
+    compression: bool,
+    cors: Option<tower_http::cors::CorsLayer>,
+    #[cfg(feature = "panic")]
+    catch_panic: Option<crate::panic::Layer>,
+    #[cfg(feature = "log-tower")]
+    trace: bool,
+    request_id: bool,
+}
+
+impl Builder {
+    #[must_use]
+    pub fn new(router: impl Into<super::Router>) -> Self {
+        Self {
+            router: router.into(),
+            config: super::Config::default(),
+            layers: Layers {
+                timeout: None,
+                body_limit: None,
+                #[cfg(feature = "server-compression")]
+                compression: false,
+                cors: None,
+                #[cfg(feature = "panic")]
+                catch_panic: Some(crate::panic::Layer::new()),
+                #[cfg(feature = "log-tower")]
+                trace: true,
+                request_id: false,
+            },
+        }
+    }
+
+    #[must_use]
+    pub fn config(mut self, config: super::Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    #[must_use]
+    pub fn timeout(mut self, timeout: impl Into<super::Timeout>) -> Self {
+        self.layers.timeout = Some(timeout.into());
+        self
+    }
+
+    #[must_use]
+    pub fn body_limit(mut self, limit: usize) -> Self {
+        self.layers.body_limit = Some(limit);
+        self
+    }
+
+    #[cfg(feature = "server-compression")]
+    #[must_use]
+    pub fn compression(mut self, enabled: bool) -> Self {
+        self.layers.compression = enabled;
+        self
+    }
+
+    #[must_use]
+    pub fn cors(mut self, cors: tower_http::cors::CorsLayer) -> Self {
+        self.layers.cors = Some(cors);
+        self
+    }
+
+    #[cfg(feature = "panic")]
+    #[must_use]
+    pub fn catch_panic(mut self, enabled: bool) -> Self {
+        self.layers.catch_panic = enabled.then(crate::panic::Layer::new);
+        self
+    }
+
+    #[cfg(feature = "panic")]
+    #[must_use]
+    pub fn panic_handler(mut self, handler: crate::panic::Layer) -> Self {
+        self.layers.catch_panic = Some(handler);
+        self
+    }
+
+    #[cfg(feature = "log-tower")]
+    #[must_use]
+    pub fn trace(mut self, enabled: bool) -> Self {
+        self.layers.trace = enabled;
+        self
+    }
+
+    #[must_use]
+    pub fn request_id(mut self, enabled: bool) -> Self {
+        self.layers.request_id = enabled;
+        self
+    }
+
+    pub async fn run(self, addr: impl Into<std::net::SocketAddr>) -> Result<(), super::Error> {
+        #[cfg(feature = "log")]
+        let start = std::time::Instant::now();
+
+        let (router, config) = self.build().await?;
+        let listener = super::listener::bind_tcp(addr.into(), None, &config.socket)?;
+        let shutdown = super::shutdown(std::future::pending())?;
+        let admin = super::admin::Running::start(config.admin.as_ref())?;
+        let _ready = super::Ready::notify();
+
+        let result = super::serve::serve(
+            listener,
+            router,
+            shutdown,
+            config,
+            #[cfg(feature = "log")]
+            start,
+        )
+        .await;
+
+        admin.stop(result).await
+    }
+
+    pub async fn spawn(
+        self,
+        addr: impl Into<std::net::SocketAddr>,
+    ) -> Result<super::Handle, super::Error> {
+        self.spawn_on(addr, tokio::sync::watch::Sender::new(false), true)
+            .await
+    }
+
+    pub(super) async fn spawn_on(
+        self,
+        addr: impl Into<std::net::SocketAddr>,
+        shutdown: tokio::sync::watch::Sender<bool>,
+        ready: bool,
+    ) -> Result<super::Handle, super::Error> {
+        #[cfg(feature = "log")]
+        let start = std::time::Instant::now();
+
+        let (router, config) = self.build().await?;
+        let listener = super::listener::bind_tcp(addr.into(), None, &config.socket)?;
+        let addr = listener.local_addr()?;
+        let trigger = super::shutdown(super::triggered(shutdown.subscribe()))?;
+        let admin = super::admin::Running::start(config.admin.as_ref())?;
+        let server = super::serve::serve(
+            listener,
+            router,
+            trigger,
+            config,
+            #[cfg(feature = "log")]
+            start,
+        );
+
+        let ready = ready.then(super::Ready::notify);
+        let server = tokio::spawn(async move {
+            let result = server.await;
+            drop(ready);
+            admin.stop(result).await
+        });
+
+        Ok(super::Handle {
+            addr,
+            shutdown,
+            server,
+        })
+    }
+
+    pub async fn run_on<L: Into<super::Listener>>(
+        self,
+        listeners: impl IntoIterator<Item = L>,
+    ) -> Result<(), super::Error> {
+        let (router, config) = self.build().await?;
+        super::multiple::serve_on(router, listeners, config).await
+    }
+
+    #[cfg(feature = "rt")]
+    pub fn start(
+        self,
+        addr: impl Into<std::net::SocketAddr>,
+        #[cfg(feature = "rt-threads")] threads: crate::rt::Threads,
+    ) -> Result<(), super::Error> {
+        crate::rt::block_on(
+            self.run(addr),
+            #[cfg(feature = "rt-threads")]
+            threads,
+        )?
+    }
+
+    #[cfg(feature = "rt")]
+    pub fn start_on<L: Into<super::Listener>>(
+        self,
+        listeners: impl IntoIterator<Item = L>,
+        #[cfg(feature = "rt-threads")] threads: crate::rt::Threads,
+    ) -> Result<(), super::Error> {
+        crate::rt::block_on(
+            self.run_on(listeners),
+            #[cfg(feature = "rt-threads")]
+            threads,
+        )?
+    }
+
+    async fn build(self) -> Result<(axum::Router, super::Config), super::Error> {
+        self.config.http.validate()?;
+
+        #[cfg(feature = "log")]
+        tracing::info!("Building router");
+
+        let router = match self.router {
+            super::Router::Simple(router) => router,
+            super::Router::Func(func) => func(),
+            super::Router::Future(future) => future.await,
+        };
+        let router = self.layers.apply(router);
+
+        #[cfg(feature = "log")]
+        tracing::info!("Router built");
+
+        Ok((router, self.config))
+    }
+}
+
+impl Layers {
+    fn apply(self, mut router: axum::Router) -> axum::Router {
+        if let Some(limit) = self.body_limit {
+            router = router
+                .layer(tower_http::limit::RequestBodyLimitLayer::new(limit))
+                .layer(axum::extract::DefaultBodyLimit::max(limit));
+        }
+
+        #[cfg(feature = "server-compression")]
+        if self.compression {
+            router = router.layer(tower_http::compression::CompressionLayer::new());
+        }
+
+        if let Some(cors) = self.cors {
+            router = router.layer(cors);
+        }
+
+        if let Some(timeout) = self.timeout {
+            router = router.layer(timeout);
+        }
+
+        #[cfg(feature = "panic")]
+        if let Some(handler) = self.catch_panic {
+            router = router.layer(handler);
+        }
+
+        #[cfg(feature = "log-tower")]
+        if self.trace {
+            router = router.layer(crate::log::tower::layer());
+        }
+
+        if self.request_id {
+            router = router
+                .layer(tower_http::request_id::PropagateRequestIdLayer::x_request_id())
+                .layer(tower_http::request_id::SetRequestIdLayer::x_request_id(
+                    tower_http::request_id::MakeRequestUuid,
+                ));
+        }
+
+        router
+    }
+}
@@ -0,0 +1,40 @@
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    pub shutdown_timeout: Option<std::time::Duration>,
+    pub socket: super::Socket,
+    pub connections: super::Connections,
+    pub http: super::Http,
+    pub admin: Option<super::Admin>,
+}
+
+impl Config {
+    #[must_use]
+    pub fn shutdown_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.shutdown_timeout = Some(timeout);
+        self
+    }
+
+    #[must_use]
+    pub fn socket(mut self, socket: super::Socket) -> Self {
+        self.socket = socket;
+        self
+    }
+
+    #[must_use]
+    pub fn connections(mut self, connections: super::Connections) -> Self {
+        self.connections = connections;
+        self
+    }
+
+    #[must_use]
+    pub fn http(mut self, http: super::Http) -> Self {
+        self.http = http;
+        self
+    }
+
+    #[must_use]
+    pub fn admin(mut self, admin: super::Admin) -> Self {
+        self.admin = Some(admin);
+        self
+    }
+}
@@ -0,0 +1,183 @@
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct Connections {
+    pub max: Option<usize>,
+    pub overflow: Overflow,
+    pub idle_timeout: Option<std::time::Duration>,
+}
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum Overflow {
+    #[default]
+    Wait,
+    Refuse,
+}
+
+pub(super) struct Limit(Option<(std::sync::Arc<tokio::sync::Semaphore>, Overflow)>);
+
+impl Limit {
+    pub(super) fn new(connections: &Connections) -> Self {
+        Self(connections.max.map(|max| {
+            (
+                std::sync::Arc::new(tokio::sync::Semaphore::new(max)),
+                connections.overflow,
+            )
+        }))
+    }
+
+    pub(super) async fn wait(&self) -> Option<tokio::sync::OwnedSemaphorePermit> {
+        match &self.0 {
+            Some((semaphore, Overflow::Wait)) => semaphore.clone().acquire_owned().await.ok(),
+            _ => None,
+        }
+    }
+
+    pub(super) fn admit(
+        &self,
+        permit: Option<tokio::sync::OwnedSemaphorePermit>,
+    ) -> Result<Option<tokio::sync::OwnedSemaphorePermit>, ()> {
+        match &self.0 {
+            Some((semaphore, Overflow::Refuse)) => semaphore
+                .clone()
+                .try_acquire_owned()
+                .map(Some)
+                .map_err(|_| {
+                    #[cfg(feature = "log")]
+                    tracing::debug!("Refusing connection, limit reached");
+                }),
+            _ => Ok(permit),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct Tracked<S> {
+    service: S,
+    activity: std::sync::Arc<Activity>,
+}
+
+impl<S> Tracked<S> {
+    pub(super) fn new(service: S) -> Self {
+        Self {
+            service,
+            activity: std::sync::Arc::new(Activity {
+                active: std::sync::atomic::AtomicUsize::new(0),
+                last: std::sync::Mutex::new(tokio::time::Instant::now()),
+            }),
+        }
+    }
+
+    pub(super) fn activity(&self) -> std::sync::Arc<Activity> {
+        self.activity.clone()
+    }
+}
+
+impl<R, S, B> hyper::service::Service<R> for Tracked<S>
+where
+    S: hyper::service::Service<R, Response = hyper::Response<B>>,
+    S::Future: Send + 'static,
+{
+    type Response = hyper::Response<Guarded<B>>;
+    type Error = S::Error;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
+
+    fn call(&self, request: R) -> Self::Future {
+        let guard = Guard::new(self.activity.clone());
+        let future = self.service.call(request);
+        Box::pin(async move {
+            future.await.map(|response| {
+                response.map(|body| Guarded {
+                    body,
+                    _guard: guard,
+                })
+            })
+        })
+    }
+}
+
+pub(super) struct Activity {
+    active: std::sync::atomic::AtomicUsize,
+    last: std::sync::Mutex<tokio::time::Instant>,
+}
+
+impl Activity {
+    fn touch(&self) {
+        *self
+            .last
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = tokio::time::Instant::now();
+    }
+
+    fn last(&self) -> tokio::time::Instant {
+        *self
+            .last
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    pub(super) async fn idle(&self, timeout: Option<std::time::Duration>) {
+        let Some(timeout) = timeout else {
+            return std::future::pending().await;
+        };
+
+        loop {
+            let deadline = self.last() + timeout;
+            if tokio::time::Instant::now() < deadline {
+                tokio::time::sleep_until(deadline).await;
+            } else if self.active.load(std::sync::atomic::Ordering::Acquire) == 0 {
+                #[cfg(feature = "log")]
+                tracing::debug!(?timeout, "Closing idle connection");
+                return;
+            } else {
+                tokio::time::sleep(timeout).await;
+            }
+        }
+    }
+}
+
+struct Guard(std::sync::Arc<Activity>);
+
+impl Guard {
+    fn new(activity: std::sync::Arc<Activity>) -> Self {
+        activity
+            .active
+            .fetch_add(1, std::sync::atomic::Ordering::AcqRel);
+        activity.touch();
+        Self(activity)
+    }
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        self.0.touch();
+        self.0
+            .active
+            .fetch_sub(1, std::sync::atomic::Ordering::AcqRel);
+    }
+}
+
+pub(super) struct Guarded<B> {
+    body: B,
+    _guard: Guard,
+}
+
+impl<B: hyper::body::Body + Unpin> hyper::body::Body for Guarded<B> {
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        mut self: std::pin::Pin<&mut Self>,
+        ctx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Result<hyper::body::Frame<Self::Data>, Self::Error>>> {
+        std::pin::Pin::new(&mut self.body).poll_frame(ctx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    fn size_hint(&self) -> hyper::body::SizeHint {
+        self.body.size_hint()
+    }
+}
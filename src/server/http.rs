@@ -0,0 +1,78 @@
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Http {
+    #[cfg(feature = "server-h1")]
+    pub header_read_timeout: Option<std::time::Duration>,
+    #[cfg(feature = "server-h1")]
+    pub max_headers: Option<usize>,
+    #[cfg(feature = "server-h1")]
+    pub max_buf_size: Option<usize>,
+    #[cfg(feature = "server-h2")]
+    pub max_header_list_size: Option<u32>,
+    #[cfg(feature = "server-h2")]
+    pub max_concurrent_streams: Option<u32>,
+    #[cfg(feature = "server-h2")]
+    pub stream_window_size: Option<u32>,
+    #[cfg(feature = "server-h2")]
+    pub connection_window_size: Option<u32>,
+}
+
+#[cfg(feature = "server-h1")]
+const MIN_BUF_SIZE: usize = 8192;
+
+impl Http {
+    #[cfg_attr(
+        not(feature = "server-h1"),
+        allow(clippy::unused_self, clippy::unnecessary_wraps)
+    )]
+    pub(super) fn validate(&self) -> Result<(), super::Error> {
+        #[cfg(feature = "server-h1")]
+        if let Some(max) = self.max_buf_size
+            && max < MIN_BUF_SIZE
+        {
+            return Err(super::Error::Config(format!(
+                "max_buf_size must be at least {MIN_BUF_SIZE} bytes, got {max}"
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn builder(
+        &self,
+    ) -> hyper_util::server::conn::auto::Builder<hyper_util::rt::TokioExecutor> {
+        let mut builder =
+            hyper_util::server::conn::auto::Builder::new(hyper_util::rt::TokioExecutor::new());
+
+        #[cfg(feature = "server-h1")]
+        {
+            let mut http1 = builder.http1();
+            http1
+                .timer(hyper_util::rt::TokioTimer::new())
+                .header_read_timeout(self.header_read_timeout);
+
+            if let Some(max) = self.max_headers {
+                http1.max_headers(max);
+            }
+
+            if let Some(max) = self.max_buf_size {
+                http1.max_buf_size(max);
+            }
+        }
+
+        #[cfg(feature = "server-h2")]
+        {
+            let mut http2 = builder.http2();
+            http2
+                .timer(hyper_util::rt::TokioTimer::new())
+                .max_concurrent_streams(self.max_concurrent_streams)
+                .initial_stream_window_size(self.stream_window_size)
+                .initial_connection_window_size(self.connection_window_size);
+
+            if let Some(max) = self.max_header_list_size {
+                http2.max_header_list_size(max);
+            }
+        }
+
+        builder
+    }
+}
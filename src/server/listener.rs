@@ -0,0 +1,260 @@
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Listener {
+    Tcp {
+        addr: std::net::SocketAddr,
+        only_v6: Option<bool>,
+    },
+    #[cfg(unix)]
+    Unix(std::path::PathBuf),
+}
+
+impl Listener {
+    #[must_use]
+    pub fn tcp(addr: impl Into<std::net::SocketAddr>) -> Self {
+        Self::Tcp {
+            addr: addr.into(),
+            only_v6: None,
+        }
+    }
+
+    #[must_use]
+    pub fn dual_stack(port: u16) -> Self {
+        Self::Tcp {
+            addr: (std::net::Ipv6Addr::UNSPECIFIED, port).into(),
+            only_v6: Some(false),
+        }
+    }
+
+    #[cfg(unix)]
+    #[must_use]
+    pub fn unix(path: impl Into<std::path::PathBuf>) -> Self {
+        Self::Unix(path.into())
+    }
+
+    #[must_use]
+    pub fn only_v6(self, only_v6: bool) -> Self {
+        match self {
+            Self::Tcp { addr, .. } => Self::Tcp {
+                addr,
+                only_v6: Some(only_v6),
+            },
+            #[cfg(unix)]
+            unix @ Self::Unix(_) => unix,
+        }
+    }
+
+    pub(super) fn bind(&self, options: &super::Socket) -> Result<Bound, super::Error> {
+        match self {
+            Self::Tcp { addr, only_v6 } => bind_tcp(*addr, *only_v6, options).map(Bound::Tcp),
+            #[cfg(unix)]
+            Self::Unix(path) => bind_unix(path, options).map(Bound::Unix),
+        }
+    }
+}
+
+impl From<std::net::SocketAddr> for Listener {
+    fn from(value: std::net::SocketAddr) -> Self {
+        Self::tcp(value)
+    }
+}
+
+#[cfg(unix)]
+impl From<std::path::PathBuf> for Listener {
+    fn from(value: std::path::PathBuf) -> Self {
+        Self::unix(value)
+    }
+}
+
+impl std::fmt::Display for Listener {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tcp { addr, .. } => addr.fmt(f),
+            #[cfg(unix)]
+            Self::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+pub(super) fn bind_tcp(
+    addr: std::net::SocketAddr,
+    only_v6: Option<bool>,
+    options: &super::Socket,
+) -> Result<Tcp, super::Error> {
+    #[cfg(feature = "log")]
+    tracing::info!(%addr, "Binding to address");
+
+    let socket = socket2::Socket::new(
+        socket2::Domain::for_address(addr),
+        socket2::Type::STREAM,
+        Some(socket2::Protocol::TCP),
+    )?;
+
+    if let Some(only_v6) = only_v6
+        && addr.is_ipv6()
+    {
+        socket.set_only_v6(only_v6)?;
+    }
+
+    #[cfg(not(windows))]
+    socket.set_reuse_address(true)?;
+    options.prepare(&socket)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(options.backlog())?;
+
+    Ok(Tcp {
+        listener: tokio::net::TcpListener::from_std(socket.into())?,
+        options: options.clone(),
+    })
+}
+
+#[cfg(unix)]
+fn bind_unix(path: &std::path::Path, options: &super::Socket) -> Result<Unix, super::Error> {
+    #[cfg(feature = "log")]
+    tracing::info!(addr = %format_args!("unix:{}", path.display()), "Binding to address");
+
+    remove_stale(path)?;
+
+    let socket = socket2::Socket::new(socket2::Domain::UNIX, socket2::Type::STREAM, None)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&socket2::SockAddr::unix(path)?)?;
+    socket.listen(options.backlog())?;
+
+    Ok(Unix {
+        listener: tokio::net::UnixListener::from_std(socket.into())?,
+        path: path.to_path_buf(),
+    })
+}
+
+#[cfg(unix)]
+fn remove_stale(path: &std::path::Path) -> Result<(), super::Error> {
+    use std::os::unix::fs::FileTypeExt;
+
+    let is_socket =
+        std::fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_socket());
+    if !is_socket {
+        return Ok(());
+    }
+
+    match std::os::unix::net::UnixStream::connect(path) {
+        Err(err) if err.kind() == std::io::ErrorKind::ConnectionRefused => {
+            #[cfg(feature = "log")]
+            tracing::warn!(path = %path.display(), "Removing stale socket");
+
+            std::fs::remove_file(path)?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+pub(super) enum Bound {
+    Tcp(Tcp),
+    #[cfg(unix)]
+    Unix(Unix),
+}
+
+impl Bound {
+    pub(super) async fn serve(
+        self,
+        router: axum::Router,
+        shutdown: impl std::future::Future<Output = ()> + Send + 'static,
+        config: super::Config,
+        #[cfg(feature = "log")] start: std::time::Instant,
+    ) -> Result<(), super::Error> {
+        match self {
+            Self::Tcp(listener) => {
+                super::serve::serve(
+                    listener,
+                    router,
+                    shutdown,
+                    config,
+                    #[cfg(feature = "log")]
+                    start,
+                )
+                .await
+            }
+            #[cfg(unix)]
+            Self::Unix(listener) => {
+                super::serve::serve(
+                    listener,
+                    router,
+                    shutdown,
+                    config,
+                    #[cfg(feature = "log")]
+                    start,
+                )
+                .await
+            }
+        }
+    }
+}
+
+pub(super) trait Accept: Send + 'static {
+    type Io: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static;
+
+    fn accept(&self) -> impl std::future::Future<Output = tokio::io::Result<Self::Io>> + Send;
+
+    #[cfg(feature = "log")]
+    fn local(&self) -> tokio::io::Result<Listener>;
+}
+
+pub(super) struct Tcp {
+    listener: tokio::net::TcpListener,
+    options: super::Socket,
+}
+
+impl Tcp {
+    pub(super) fn local_addr(&self) -> tokio::io::Result<std::net::SocketAddr> {
+        self.listener.local_addr()
+    }
+}
+
+impl Accept for Tcp {
+    type Io = tokio::net::TcpStream;
+
+    async fn accept(&self) -> tokio::io::Result<Self::Io> {
+        let (stream, _) = self.listener.accept().await?;
+
+        #[cfg_attr(not(feature = "log"), allow(unused_variables))]
+        if let Err(err) = self.options.accepted(&stream) {
+            #[cfg(feature = "log")]
+            tracing::debug!(error = %err, "Could not configure accepted socket");
+        }
+
+        Ok(stream)
+    }
+
+    #[cfg(feature = "log")]
+    fn local(&self) -> tokio::io::Result<Listener> {
+        self.local_addr().map(Listener::tcp)
+    }
+}
+
+#[cfg(unix)]
+pub(super) struct Unix {
+    listener: tokio::net::UnixListener,
+    path: std::path::PathBuf,
+}
+
+#[cfg(unix)]
+impl Accept for Unix {
+    type Io = tokio::net::UnixStream;
+
+    async fn accept(&self) -> tokio::io::Result<Self::Io> {
+        self.listener.accept().await.map(|(stream, _)| stream)
+    }
+
+    #[cfg(feature = "log")]
+    fn local(&self) -> tokio::io::Result<Listener> {
+        Ok(Listener::unix(self.path.clone()))
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Unix {
+    fn drop(&mut self) {
+        drop(std::fs::remove_file(&self.path));
+    }
+}
@@ -1,3 +1,29 @@
+mod admin;
+mod builder;
+mod config;
+mod connections;
+mod http;
+mod listener;
+mod multiple;
+mod serve;
+mod socket;
+mod timeout;
+
+pub use admin::Admin;
+pub use builder::Builder;
+pub use config::Config;
+pub use connections::{Connections, Overflow};
+pub use http::Http;
+pub use listener::Listener;
+pub use multiple::{Failures, Policy, run_multiple, run_on};
+pub use socket::{Keepalive, Socket};
+pub use timeout::{Timed, Timeout};
+
+pub const BIND: std::net::SocketAddr = std::net::SocketAddr::V4(std::net::SocketAddrV4::new(
+    std::net::Ipv4Addr::UNSPECIFIED,
+    8080,
+));
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[cfg(feature = "rt-shutdown")]
@@ -6,57 +32,131 @@ pub enum Error {
     #[cfg(feature = "rt")]
     #[error(transparent)]
     Runtime(#[from] crate::rt::Error),
-    #[cfg(feature = "rt")]
     #[error("Could not join task: {0}")]
     TaskJoin(#[from] tokio::task::JoinError),
     #[error(transparent)]
     Server(#[from] hyper::Error),
     #[error(transparent)]
     Io(#[from] tokio::io::Error),
+    #[error(transparent)]
+    Servers(#[from] Failures),
+    #[error("Invalid server config: {0}")]
+    Config(String),
 }
 
 pub async fn run(
     router: impl Into<Router>,
     addr: impl Into<std::net::SocketAddr>,
 ) -> Result<(), Error> {
-    #[cfg(feature = "log")]
-    let start = std::time::Instant::now();
+    run_with(router, addr, Config::default()).await
+}
 
-    #[cfg(feature = "log")]
-    tracing::info!("Building router");
+pub async fn run_with(
+    router: impl Into<Router>,
+    addr: impl Into<std::net::SocketAddr>,
+    config: Config,
+) -> Result<(), Error> {
+    Builder::new(router).config(config).run(addr).await
+}
+
+pub async fn spawn(
+    router: impl Into<Router>,
+    addr: impl Into<std::net::SocketAddr>,
+) -> Result<Handle, Error> {
+    spawn_with(router, addr, Config::default()).await
+}
 
-    let router = match router.into() {
-        Router::Simple(router) => router,
-        Router::Func(func) => func(),
-        Router::Future(future) => future.await,
+pub async fn spawn_with(
+    router: impl Into<Router>,
+    addr: impl Into<std::net::SocketAddr>,
+    config: Config,
+) -> Result<Handle, Error> {
+    Builder::new(router).config(config).spawn(addr).await
+}
+
+#[cfg_attr(not(feature = "rt-shutdown"), allow(clippy::unnecessary_wraps))]
+fn shutdown(
+    trigger: impl std::future::Future<Output = ()> + Send + 'static,
+) -> Result<impl std::future::Future<Output = ()> + Send + 'static, Error> {
+    #[cfg(feature = "rt-shutdown")]
+    let trigger = {
+        let token = crate::rt::shutdown::listen()?;
+        async move {
+            tokio::select! {
+                () = token.cancelled() => {}
+                () = trigger => {}
+            }
+        }
     };
 
-    #[cfg(feature = "panic")]
-    let router = router.layer(crate::panic::CatchPanicLayer::new());
+    Ok(trigger)
+}
+
+struct Ready(#[cfg(all(feature = "systemd", unix))] tokio::task::JoinHandle<()>);
 
-    #[cfg(feature = "log-tower")]
-    let router = router.layer(crate::log::tower::layer());
+impl Ready {
+    fn notify() -> Self {
+        #[cfg(all(feature = "systemd", unix))]
+        {
+            #[cfg_attr(not(feature = "log"), allow(unused_variables))]
+            if let Err(err) = crate::systemd::ready() {
+                #[cfg(feature = "log")]
+                tracing::warn!(error = %err, "Could not notify systemd of readiness");
+            }
+            Self(tokio::spawn(crate::systemd::watchdog()))
+        }
 
-    #[cfg(feature = "log")]
-    tracing::info!("Router built");
+        #[cfg(not(all(feature = "systemd", unix)))]
+        Self()
+    }
+}
 
-    let addr = addr.into();
+impl Drop for Ready {
+    fn drop(&mut self) {
+        #[cfg(all(feature = "systemd", unix))]
+        self.0.abort();
+    }
+}
 
-    #[cfg(feature = "log")]
-    tracing::info!(%addr, "Binding to address");
+async fn triggered(mut receiver: tokio::sync::watch::Receiver<bool>) {
+    if receiver.wait_for(|shutdown| *shutdown).await.is_err() {
+        std::future::pending::<()>().await;
+    }
+}
 
-    let listener = tokio::net::TcpListener::bind(&addr).await?;
-    let server = axum::serve(listener, router.into_make_service());
+pub struct Handle {
+    addr: std::net::SocketAddr,
+    shutdown: tokio::sync::watch::Sender<bool>,
+    server: tokio::task::JoinHandle<Result<(), Error>>,
+}
 
-    #[cfg(feature = "rt-shutdown")]
-    let server = server.with_graceful_shutdown(crate::rt::Shutdown::new()?);
+impl Handle {
+    #[must_use]
+    pub fn local_addr(&self) -> std::net::SocketAddr {
+        self.addr
+    }
 
-    server.await?;
+    pub fn shutdown(&self) {
+        #[cfg(feature = "log")]
+        tracing::info!(addr = %self.addr, "Shutdown requested");
 
-    #[cfg(feature = "log")]
-    tracing::info!(duration = ?start.elapsed(), "Server gracefully shutdown");
+        self.shutdown.send_replace(true);
+    }
+}
+
+impl std::future::Future for Handle {
+    type Output = Result<(), Error>;
 
-    Ok(())
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        ctx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        match std::pin::Pin::new(&mut self.server).poll(ctx) {
+            std::task::Poll::Ready(Ok(result)) => std::task::Poll::Ready(result),
+            std::task::Poll::Ready(Err(err)) => std::task::Poll::Ready(Err(Error::TaskJoin(err))),
+            std::task::Poll::Pending => std::task::Poll::Pending,
+        }
+    }
 }
 
 pub enum Router {
@@ -102,11 +202,41 @@ pub fn start(
     addr: impl Into<std::net::SocketAddr>,
     #[cfg(feature = "rt-threads")] threads: crate::rt::Threads,
 ) -> Result<(), Error> {
-    crate::rt::block_on(
-        run(router, addr),
+    start_with(
+        router,
+        addr,
+        Config::default(),
         #[cfg(feature = "rt-threads")]
         threads,
-    )?
+    )
+}
+
+#[cfg(feature = "rt")]
+pub fn start_with(
+    router: impl Into<Router>,
+    addr: impl Into<std::net::SocketAddr>,
+    config: Config,
+    #[cfg(feature = "rt-threads")] threads: crate::rt::Threads,
+) -> Result<(), Error> {
+    Builder::new(router).config(config).start(
+        addr,
+        #[cfg(feature = "rt-threads")]
+        threads,
+    )
+}
+
+#[cfg(feature = "rt")]
+pub fn start_on<L: Into<Listener>>(
+    router: impl Into<Router>,
+    listeners: impl IntoIterator<Item = L>,
+    config: Config,
+    #[cfg(feature = "rt-threads")] threads: crate::rt::Threads,
+) -> Result<(), Error> {
+    Builder::new(router).config(config).start_on(
+        listeners,
+        #[cfg(feature = "rt-threads")]
+        threads,
+    )
 }
 
 #[cfg(feature = "rt")]
@@ -114,25 +244,23 @@ pub fn start_multiple(
     servers: impl Iterator<Item = (std::net::SocketAddr, Router)>,
     #[cfg(feature = "rt-threads")] threads: crate::rt::Threads,
 ) -> Result<(), Error> {
-    crate::rt::block_on(
-        spawn_servers(servers),
+    start_multiple_with(
+        servers,
+        Policy::default(),
         #[cfg(feature = "rt-threads")]
         threads,
-    )?
+    )
 }
 
 #[cfg(feature = "rt")]
-async fn spawn_servers(
+pub fn start_multiple_with(
     servers: impl Iterator<Item = (std::net::SocketAddr, Router)>,
+    policy: Policy,
+    #[cfg(feature = "rt-threads")] threads: crate::rt::Threads,
 ) -> Result<(), Error> {
-    let mut result = Ok(());
-    let servers = servers
-        .map(|(addr, router)| tokio::spawn(run(router, addr)))
-        .collect::<Vec<_>>();
-    for server in servers {
-        if let Err(e) = server.await {
-            result = Err(Error::TaskJoin(e));
-        }
-    }
-    result
+    crate::rt::block_on(
+        run_multiple(servers, policy),
+        #[cfg(feature = "rt-threads")]
+        threads,
+    )?
 }
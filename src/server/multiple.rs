@@ -0,0 +1,211 @@
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum Policy {
+    #[default]
+    FailFast,
+    KeepRunning,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("{} server(s) failed{}", .0.len(), summary(.0))]
+pub struct Failures(pub Vec<(super::Listener, super::Error)>);
+
+fn summary(failures: &[(super::Listener, super::Error)]) -> String {
+    failures
+        .iter()
+        .map(|(addr, err)| format!("; {addr}: {err}"))
+        .collect::<Vec<_>>()
+        .concat()
+}
+
+pub async fn run_multiple(
+    servers: impl IntoIterator<Item = (std::net::SocketAddr, super::Router)>,
+    policy: Policy,
+) -> Result<(), super::Error> {
+    let mut supervisor = Supervisor::new(policy);
+
+    for (addr, router) in servers {
+        let spawned = super::Builder::new(router)
+            .spawn_on(addr, supervisor.shutdown.clone(), false)
+            .await;
+
+        match spawned {
+            Ok(handle) => supervisor.spawn(super::Listener::tcp(addr), handle),
+            Err(err) => {
+                if !supervisor.fail(super::Listener::tcp(addr), err) {
+                    break;
+                }
+            }
+        }
+    }
+
+    let _ready = (!supervisor.running.is_empty()
+        && (supervisor.failures.is_empty() || policy == Policy::KeepRunning))
+        .then(super::Ready::notify);
+    supervisor.join().await
+}
+
+pub async fn run_on<L: Into<super::Listener>>(
+    router: impl Into<super::Router>,
+    listeners: impl IntoIterator<Item = L>,
+    config: super::Config,
+) -> Result<(), super::Error> {
+    super::Builder::new(router)
+        .config(config)
+        .run_on(listeners)
+        .await
+}
+
+pub(super) async fn serve_on<L: Into<super::Listener>>(
+    router: axum::Router,
+    listeners: impl IntoIterator<Item = L>,
+    config: super::Config,
+) -> Result<(), super::Error> {
+    let mut supervisor = Supervisor::new(Policy::FailFast);
+
+    let mut bound = Vec::new();
+    for listener in listeners {
+        let listener = listener.into();
+        match listener.bind(&config.socket) {
+            Ok(server) => {
+                let shutdown = super::shutdown(super::triggered(supervisor.shutdown.subscribe()))?;
+                bound.push((listener, server, shutdown));
+            }
+            Err(err) => {
+                supervisor.fail(listener, err);
+                return supervisor.join().await;
+            }
+        }
+    }
+
+    let admin = super::admin::Running::start(config.admin.as_ref())?;
+
+    for (listener, server, shutdown) in bound {
+        let server = server.serve(
+            router.clone(),
+            shutdown,
+            config.clone(),
+            #[cfg(feature = "log")]
+            supervisor.start,
+        );
+        supervisor.spawn(listener, server);
+    }
+
+    let ready = super::Ready::notify();
+    let result = supervisor.join().await;
+    drop(ready);
+    admin.stop(result).await
+}
+
+struct Supervisor {
+    policy: Policy,
+    shutdown: tokio::sync::watch::Sender<bool>,
+    failures: Vec<(super::Listener, super::Error)>,
+    listeners: std::collections::HashMap<tokio::task::Id, super::Listener>,
+    running: tokio::task::JoinSet<Result<(), super::Error>>,
+    #[cfg(feature = "rt-shutdown")]
+    token: crate::rt::shutdown::Token,
+    #[cfg(feature = "log")]
+    start: std::time::Instant,
+}
+
+impl Supervisor {
+    fn new(policy: Policy) -> Self {
+        Self {
+            policy,
+            shutdown: tokio::sync::watch::Sender::new(false),
+            failures: Vec::new(),
+            listeners: std::collections::HashMap::new(),
+            running: tokio::task::JoinSet::new(),
+            #[cfg(feature = "rt-shutdown")]
+            token: crate::rt::shutdown::token(),
+            #[cfg(feature = "log")]
+            start: std::time::Instant::now(),
+        }
+    }
+
+    fn spawn(
+        &mut self,
+        listener: super::Listener,
+        server: impl std::future::Future<Output = Result<(), super::Error>> + Send + 'static,
+    ) {
+        let id = self.running.spawn(server).id();
+        self.listeners.insert(id, listener);
+    }
+
+    fn fail(&mut self, listener: super::Listener, err: super::Error) -> bool {
+        #[cfg(feature = "log")]
+        tracing::error!(addr = %listener, error = %err, "Could not start server");
+
+        self.failures.push((listener, err));
+        self.policy == Policy::KeepRunning
+    }
+
+    async fn join(mut self) -> Result<(), super::Error> {
+        #[cfg(feature = "log")]
+        let total = self.listeners.len() + self.failures.len();
+
+        if self.policy == Policy::FailFast && !self.failures.is_empty() {
+            self.stop();
+        }
+
+        while let Some(result) = self.running.join_next_with_id().await {
+            let (id, result) = match result {
+                Ok((id, result)) => (id, result),
+                Err(err) => (err.id(), Err(super::Error::TaskJoin(err))),
+            };
+            let listener = self.listeners[&id].clone();
+
+            #[cfg(feature = "rt-shutdown")]
+            let expected = *self.shutdown.borrow() || self.token.is_cancelled();
+            #[cfg(not(feature = "rt-shutdown"))]
+            let expected = *self.shutdown.borrow();
+
+            match result {
+                Ok(()) if !expected => {
+                    #[cfg(feature = "log")]
+                    tracing::warn!(addr = %listener, "Server exited unexpectedly");
+                }
+                Ok(()) => {}
+                Err(err) => {
+                    #[cfg(feature = "log")]
+                    tracing::error!(addr = %listener, error = %err, "Server failed");
+
+                    self.failures.push((listener, err));
+                }
+            }
+
+            if !expected && self.policy == Policy::FailFast {
+                self.stop();
+            }
+        }
+
+        #[cfg(feature = "log")]
+        if self.failures.is_empty() {
+            tracing::info!(
+                servers = total,
+                duration = ?self.start.elapsed(),
+                "All servers shutdown"
+            );
+        } else {
+            tracing::warn!(
+                servers = total,
+                failed = self.failures.len(),
+                duration = ?self.start.elapsed(),
+                "All servers shutdown"
+            );
+        }
+
+        if self.failures.is_empty() {
+            Ok(())
+        } else {
+            Err(super::Error::Servers(Failures(self.failures)))
+        }
+    }
+
+    fn stop(&self) {
+        if !self.shutdown.send_replace(true) && !self.running.is_empty() {
+            #[cfg(feature = "log")]
+            tracing::warn!(servers = self.running.len(), "Stopping remaining servers");
+        }
+    }
+}
@@ -0,0 +1,183 @@
+pub async fn serve<L: super::listener::Accept>(
+    listener: L,
+    router: axum::Router,
+    shutdown: impl std::future::Future<Output = ()> + Send + 'static,
+    config: super::Config,
+    #[cfg(feature = "log")] start: std::time::Instant,
+) -> Result<(), super::Error> {
+    #[cfg(feature = "log")]
+    let addr = listener.local()?;
+
+    let builder = config.http.builder();
+    let limit = super::connections::Limit::new(&config.connections);
+    let idle_timeout = config.connections.idle_timeout;
+    let (signal, _) = tokio::sync::watch::channel(());
+    let mut connections = tokio::task::JoinSet::new();
+
+    let mut shutdown = std::pin::pin!(shutdown);
+    loop {
+        let permit = tokio::select! {
+            permit = limit.wait() => permit,
+            () = &mut shutdown => break,
+        };
+
+        let stream = tokio::select! {
+            result = listener.accept() => match result {
+                Ok(stream) => stream,
+                Err(err) => {
+                    accept_error(err).await;
+                    continue;
+                }
+            },
+            () = &mut shutdown => break,
+        };
+
+        let Ok(permit) = limit.admit(permit) else {
+            drop(stream);
+            continue;
+        };
+
+        while connections.try_join_next().is_some() {}
+
+        connections.spawn(connection(
+            stream,
+            builder.clone(),
+            router.clone(),
+            signal.subscribe(),
+            idle_timeout,
+            permit,
+        ));
+    }
+
+    drop(listener);
+    while connections.try_join_next().is_some() {}
+
+    if let Some(admin) = &config.admin {
+        admin.drain();
+    }
+
+    #[cfg(feature = "log")]
+    tracing::info!(
+        %addr,
+        connections = connections.len(),
+        "Shutting down server, draining connections"
+    );
+
+    signal.send_replace(());
+
+    let force = force();
+
+    let forced = tokio::select! {
+        () = drain(&mut connections) => false,
+        () = deadline(config.shutdown_timeout) => true,
+        () = force => {
+            #[cfg(feature = "log")]
+            tracing::warn!("Received second shutdown signal");
+            true
+        }
+    };
+
+    if forced {
+        #[cfg(feature = "log")]
+        tracing::warn!(
+            %addr,
+            connections = connections.len(),
+            "Forcibly closing remaining connections"
+        );
+
+        connections.shutdown().await;
+    }
+
+    #[cfg(feature = "log")]
+    if forced {
+        tracing::warn!(%addr, duration = ?start.elapsed(), "Server forcibly shutdown");
+    } else {
+        tracing::info!(%addr, duration = ?start.elapsed(), "Server gracefully shutdown");
+    }
+
+    Ok(())
+}
+
+async fn connection<I: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static>(
+    stream: I,
+    builder: hyper_util::server::conn::auto::Builder<hyper_util::rt::TokioExecutor>,
+    router: axum::Router,
+    mut signal: tokio::sync::watch::Receiver<()>,
+    idle_timeout: Option<std::time::Duration>,
+    permit: Option<tokio::sync::OwnedSemaphorePermit>,
+) {
+    let service =
+        super::connections::Tracked::new(hyper_util::service::TowerToHyperService::new(router));
+    let activity = service.activity();
+    let connection =
+        builder.serve_connection_with_upgrades(hyper_util::rt::TokioIo::new(stream), service);
+    let mut connection = std::pin::pin!(connection);
+
+    let result = tokio::select! {
+        result = connection.as_mut() => result,
+        _ = signal.changed() => {
+            connection.as_mut().graceful_shutdown();
+            connection.await
+        }
+        () = activity.idle(idle_timeout) => {
+            connection.as_mut().graceful_shutdown();
+            connection.await
+        }
+    };
+
+    drop(permit);
+
+    #[cfg(feature = "log")]
+    if let Err(err) = result {
+        tracing::debug!(error = %err, "Connection closed with error");
+    }
+    #[cfg(not(feature = "log"))]
+    drop(result);
+}
+
+async fn force() {
+    #[cfg(feature = "rt-shutdown")]
+    match crate::rt::Shutdown::new() {
+        Ok(signal) => return signal.await,
+        #[cfg_attr(not(feature = "log"), allow(unused_variables))]
+        Err(err) => {
+            #[cfg(feature = "log")]
+            tracing::warn!(error = %err, "Could not listen for second shutdown signal");
+        }
+    }
+
+    std::future::pending::<()>().await;
+}
+
+async fn drain(connections: &mut tokio::task::JoinSet<()>) {
+    while connections.join_next().await.is_some() {}
+}
+
+async fn deadline(timeout: Option<std::time::Duration>) {
+    if let Some(timeout) = timeout {
+        tokio::time::sleep(timeout).await;
+
+        #[cfg(feature = "log")]
+        tracing::warn!(?timeout, "Shutdown timeout elapsed");
+    } else {
+        std::future::pending::<()>().await;
+    }
+}
+
+async fn accept_error(err: tokio::io::Error) {
+    if matches!(
+        err.kind(),
+        tokio::io::ErrorKind::ConnectionRefused
+            | tokio::io::ErrorKind::ConnectionAborted
+            | tokio::io::ErrorKind::ConnectionReset
+    ) {
+        return;
+    }
+
+    #[cfg(feature = "log")]
+    tracing::error!(error = %err, "Could not accept connection");
+    #[cfg(not(feature = "log"))]
+    drop(err);
+
+    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+}
@@ -0,0 +1,104 @@
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Socket {
+    pub reuse_port: bool,
+    pub backlog: Option<u32>,
+    pub nodelay: Option<bool>,
+    pub keepalive: Option<Keepalive>,
+    pub recv_buffer_size: Option<usize>,
+    pub send_buffer_size: Option<usize>,
+}
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct Keepalive {
+    pub time: Option<std::time::Duration>,
+    pub interval: Option<std::time::Duration>,
+    pub retries: Option<u32>,
+}
+
+impl Socket {
+    pub(super) fn prepare(&self, socket: &socket2::Socket) -> tokio::io::Result<()> {
+        if self.reuse_port {
+            #[cfg(all(
+                unix,
+                not(any(target_os = "solaris", target_os = "illumos", target_os = "cygwin"))
+            ))]
+            socket.set_reuse_port(true)?;
+            #[cfg(not(all(
+                unix,
+                not(any(target_os = "solaris", target_os = "illumos", target_os = "cygwin"))
+            )))]
+            return Err(tokio::io::Error::new(
+                tokio::io::ErrorKind::Unsupported,
+                "SO_REUSEPORT is not supported on this platform",
+            ));
+        }
+
+        self.buffers(&socket2::SockRef::from(socket))
+    }
+
+    pub(super) fn accepted(&self, stream: &tokio::net::TcpStream) -> tokio::io::Result<()> {
+        let socket = socket2::SockRef::from(stream);
+
+        if let Some(nodelay) = self.nodelay {
+            socket.set_tcp_nodelay(nodelay)?;
+        }
+
+        if let Some(keepalive) = self.keepalive {
+            socket.set_tcp_keepalive(&keepalive.into())?;
+        }
+
+        self.buffers(&socket)
+    }
+
+    fn buffers(&self, socket: &socket2::SockRef<'_>) -> tokio::io::Result<()> {
+        if let Some(size) = self.recv_buffer_size {
+            socket.set_recv_buffer_size(size)?;
+        }
+
+        if let Some(size) = self.send_buffer_size {
+            socket.set_send_buffer_size(size)?;
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn backlog(&self) -> i32 {
+        self.backlog
+            .map_or(1024, |backlog| i32::try_from(backlog).unwrap_or(i32::MAX))
+    }
+}
+
+impl From<Keepalive> for socket2::TcpKeepalive {
+    fn from(value: Keepalive) -> Self {
+        let mut keepalive = Self::new();
+
+        if let Some(time) = value.time {
+            keepalive = keepalive.with_time(time);
+        }
+
+        #[cfg(any(
+            target_os = "android",
+            target_os = "freebsd",
+            target_os = "ios",
+            target_os = "linux",
+            target_os = "macos",
+            target_os = "windows",
+        ))]
+        if let Some(interval) = value.interval {
+            keepalive = keepalive.with_interval(interval);
+        }
+
+        #[cfg(any(
+            target_os = "android",
+            target_os = "freebsd",
+            target_os = "ios",
+            target_os = "linux",
+            target_os = "macos",
+        ))]
+        if let Some(retries) = value.retries {
+            keepalive = keepalive.with_retries(retries);
+        }
+
+        keepalive
+    }
+}
@@ -0,0 +1,164 @@
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Timeout {
+    duration: std::time::Duration,
+    status: axum::http::StatusCode,
+    retry_after: Option<std::time::Duration>,
+}
+
+impl Timeout {
+    #[must_use]
+    pub fn new(duration: std::time::Duration) -> Self {
+        Self {
+            duration,
+            status: axum::http::StatusCode::REQUEST_TIMEOUT,
+            retry_after: None,
+        }
+    }
+
+    #[must_use]
+    pub fn unavailable(mut self) -> Self {
+        self.status = axum::http::StatusCode::SERVICE_UNAVAILABLE;
+        self
+    }
+
+    #[must_use]
+    pub fn retry_after(mut self, retry_after: std::time::Duration) -> Self {
+        self.retry_after = Some(retry_after);
+        self
+    }
+
+    fn response(&self) -> axum::response::Response {
+        let retry_after = self
+            .retry_after
+            .map(|retry| retry.as_secs() + u64::from(retry.subsec_nanos() > 0));
+        let body = format!(
+            r#"{{"status":{},"error":"{}","message":"Request did not complete within {:?}","retry_after":{}}}"#,
+            self.status.as_u16(),
+            self.status.canonical_reason().unwrap_or_default(),
+            self.duration,
+            retry_after.map_or_else(|| String::from("null"), |retry| retry.to_string()),
+        );
+
+        let mut response = axum::response::Response::new(axum::body::Body::from(body));
+        *response.status_mut() = self.status;
+        response.headers_mut().insert(
+            axum::http::header::CONTENT_TYPE,
+            axum::http::HeaderValue::from_static("application/json"),
+        );
+        if let Some(retry_after) = retry_after {
+            response
+                .headers_mut()
+                .insert(axum::http::header::RETRY_AFTER, retry_after.into());
+        }
+        response
+    }
+}
+
+impl From<std::time::Duration> for Timeout {
+    fn from(value: std::time::Duration) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<S> tower::Layer<S> for Timeout {
+    type Service = Timed<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Timed {
+            inner,
+            timeout: *self,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Timed<S> {
+    inner: S,
+    timeout: Timeout,
+}
+
+impl<S> tower::Service<axum::extract::Request> for Timed<S>
+where
+    S: tower::Service<
+            axum::extract::Request,
+            Response = axum::response::Response,
+            Error = std::convert::Infallible,
+        > + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = axum::response::Response;
+    type Error = std::convert::Infallible;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
+
+    fn poll_ready(
+        &mut self,
+        ctx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(ctx)
+    }
+
+    fn call(&mut self, mut request: axum::extract::Request) -> Self::Future {
+        let inner = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, inner);
+
+        if let Some(deadline) = request.extensions().get::<Deadline>() {
+            deadline.set(self.timeout);
+            return Box::pin(inner.call(request));
+        }
+
+        let start = tokio::time::Instant::now();
+        let deadline = Deadline::new(self.timeout);
+        request.extensions_mut().insert(deadline.clone());
+        let response = inner.call(request);
+
+        Box::pin(async move {
+            tokio::pin!(response);
+            loop {
+                let timeout = deadline.get();
+                tokio::select! {
+                    response = &mut response => return response,
+                    () = tokio::time::sleep_until(start + timeout.duration) => {
+                        #[cfg(feature = "log")]
+                        tracing::warn!(timeout = ?timeout.duration, status = timeout.status.as_u16(), "Request timed out");
+
+                        return Ok(timeout.response());
+                    }
+                    () = deadline.0.1.notified() => {}
+                }
+            }
+        })
+    }
+}
+
+#[derive(Clone)]
+struct Deadline(std::sync::Arc<(std::sync::Mutex<Timeout>, tokio::sync::Notify)>);
+
+impl Deadline {
+    fn new(timeout: Timeout) -> Self {
+        Self(std::sync::Arc::new((
+            std::sync::Mutex::new(timeout),
+            tokio::sync::Notify::new(),
+        )))
+    }
+
+    fn get(&self) -> Timeout {
+        *self
+            .0
+            .0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn set(&self, timeout: Timeout) {
+        *self
+            .0
+            .0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = timeout;
+        self.0.1.notify_one();
+    }
+}
@@ -0,0 +1,124 @@
+#[derive(Debug, thiserror::Error)]
+#[error("Could not notify systemd: {0}")]
+pub struct Error(#[from] std::io::Error);
+
+pub struct Notifier(std::os::unix::net::UnixDatagram);
+
+impl Notifier {
+    pub fn from_env() -> Result<Option<Self>, Error> {
+        std::env::var_os("NOTIFY_SOCKET")
+            .map(Self::connect)
+            .transpose()
+    }
+
+    pub fn connect(path: impl AsRef<std::ffi::OsStr>) -> Result<Self, Error> {
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = path.as_ref();
+        let socket = std::os::unix::net::UnixDatagram::unbound()?;
+
+        if let Some(name) = path.as_bytes().strip_prefix(b"@") {
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            {
+                #[cfg(target_os = "android")]
+                use std::os::android::net::SocketAddrExt;
+                #[cfg(target_os = "linux")]
+                use std::os::linux::net::SocketAddrExt;
+
+                socket.connect_addr(&std::os::unix::net::SocketAddr::from_abstract_name(name)?)?;
+            }
+            #[cfg(not(any(target_os = "linux", target_os = "android")))]
+            {
+                let _ = name;
+                return Err(Error(std::io::Error::new(
+                    std::io::ErrorKind::Unsupported,
+                    "Abstract sockets are not supported on this platform",
+                )));
+            }
+        } else {
+            socket.connect(path)?;
+        }
+
+        Ok(Self(socket))
+    }
+
+    pub fn notify(&self, state: &str) -> Result<(), Error> {
+        self.0.send(state.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn ready(&self) -> Result<(), Error> {
+        self.notify("READY=1")
+    }
+
+    pub fn stopping(&self) -> Result<(), Error> {
+        self.notify("STOPPING=1")
+    }
+
+    pub fn watchdog(&self) -> Result<(), Error> {
+        self.notify("WATCHDOG=1")
+    }
+}
+
+pub fn notify(state: &str) -> Result<bool, Error> {
+    Notifier::from_env()?
+        .map(|notifier| notifier.notify(state))
+        .transpose()
+        .map(|sent| sent.is_some())
+}
+
+pub fn ready() -> Result<bool, Error> {
+    notify("READY=1")
+}
+
+pub fn stopping() -> Result<bool, Error> {
+    notify("STOPPING=1")
+}
+
+#[must_use]
+pub fn watchdog_interval() -> Option<std::time::Duration> {
+    if let Some(pid) = std::env::var_os("WATCHDOG_PID")
+        && pid.to_str()?.parse::<u32>().ok()? != std::process::id()
+    {
+        return None;
+    }
+
+    std::env::var_os("WATCHDOG_USEC")?
+        .to_str()?
+        .parse::<u64>()
+        .ok()
+        .filter(|usec| *usec > 0)
+        .map(std::time::Duration::from_micros)
+}
+
+pub async fn watchdog() {
+    let Some(interval) = watchdog_interval() else {
+        return std::future::pending().await;
+    };
+
+    let notifier = match Notifier::from_env() {
+        Ok(Some(notifier)) => notifier,
+        Ok(None) => return std::future::pending().await,
+        #[cfg_attr(not(feature = "log"), allow(unused_variables))]
+        Err(err) => {
+            #[cfg(feature = "log")]
+            tracing::warn!(error = %err, "Could not start systemd watchdog");
+            return std::future::pending().await;
+        }
+    };
+
+    #[cfg(feature = "log")]
+    tracing::info!(?interval, "Starting systemd watchdog");
+
+    let mut ticker = tokio::time::interval(interval / 2);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+        #[cfg_attr(not(feature = "log"), allow(unused_variables))]
+        if let Err(err) = notifier.watchdog() {
+            #[cfg(feature = "log")]
+            tracing::warn!(error = %err, "Could not ping systemd watchdog");
+        }
+    }
+}